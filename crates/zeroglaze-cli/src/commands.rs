@@ -0,0 +1,599 @@
+use std::path::PathBuf;
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+use zeroglaze_client::accounts::{
+    decode, BondingCurve, BuyerRecord, CurveReserves, PlatformState, TokenState,
+};
+use zeroglaze_client::instructions::{self, CreateTokenArgs};
+use zeroglaze_client::quote::{self, quote_buy, quote_buy_for_sol, quote_sell};
+use zeroglaze_client::zeroglaze::constants::CURVE_SUPPLY;
+use zeroglaze_client::zeroglaze::legacy::BondingCurveV0;
+use zeroglaze_client::{pda, TokenAccounts, PROGRAM_ID};
+
+use crate::{Cli, Command, StreamStatus};
+
+/// Offset of `BondingCurve::token_mint`: discriminator, then `creator`
+const CURVE_TOKEN_MINT_OFFSET: usize = 8 + 32;
+
+struct Cluster {
+    rpc: RpcClient,
+    keypair: Option<PathBuf>,
+}
+
+impl Cluster {
+    fn signer(&self) -> Result<Keypair> {
+        let path = match &self.keypair {
+            Some(path) => path.clone(),
+            None => {
+                let home = std::env::var("HOME").context("HOME is not set; pass --keypair")?;
+                PathBuf::from(home).join(".config/solana/id.json")
+            }
+        };
+        read_keypair_file(&path).map_err(|e| anyhow!("reading keypair {}: {e}", path.display()))
+    }
+
+    fn send(&self, instructions: &[Instruction], signer: &Keypair) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&tx)?)
+    }
+
+    fn platform(&self) -> Result<PlatformState> {
+        let data = self.rpc.get_account_data(&pda::platform_state())
+            .context("platform state not found; run init-platform first")?;
+        Ok(decode(&data)?)
+    }
+
+    fn clock(&self) -> Result<Clock> {
+        let account = self.rpc.get_account(&sysvar::clock::ID)?;
+        solana_sdk::account::from_account(&account).ok_or_else(|| anyhow!("invalid clock sysvar"))
+    }
+
+    /// Find a token's accounts from its mint
+    fn token(&self, mint: &Pubkey) -> Result<(TokenAccounts, TokenState)> {
+        let curves = self.curves(Some(mint))?;
+        let (address, curve) = curves
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("no bonding curve for mint {mint}"))?;
+        let Curve::Current(curve) = curve else {
+            bail!("bonding curve {address} needs migration");
+        };
+        let token = TokenAccounts::new(&curve.creator);
+        let state = self.token_state(&token)?;
+        Ok((token, state))
+    }
+
+    fn token_state(&self, token: &TokenAccounts) -> Result<TokenState> {
+        let accounts = self.rpc.get_multiple_accounts(&[
+            token.bonding_curve,
+            token.curve_reserves,
+            token.token_metadata,
+        ])?;
+        let [curve, reserves, metadata] = [0, 1, 2].map(|i| {
+            accounts[i].as_ref().map(|account| account.data.as_slice()).unwrap_or_default()
+        });
+        TokenState::decode(curve, reserves, metadata)
+            .with_context(|| format!("decoding accounts of curve {}", token.bonding_curve))
+    }
+
+    /// Every bonding curve, or the one for `mint`
+    fn curves(&self, mint: Option<&Pubkey>) -> Result<Vec<(Pubkey, Curve)>> {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            BondingCurve::DISCRIMINATOR.to_vec(),
+        ))];
+        if let Some(mint) = mint {
+            filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                CURVE_TOKEN_MINT_OFFSET,
+                mint.to_bytes().to_vec(),
+            )));
+        }
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self.rpc.get_program_accounts_with_config(&PROGRAM_ID, config)?;
+        Ok(accounts
+            .into_iter()
+            .map(|(address, account)| (address, Curve::decode(&account.data)))
+            .collect())
+    }
+}
+
+/// A bonding curve account, which may predate the current layout
+enum Curve {
+    Current(BondingCurve),
+    /// Pre-versioning layout, waiting for migrate_curve
+    Legacy(BondingCurveV0),
+    /// Neither layout decodes
+    Unknown,
+}
+
+impl Curve {
+    fn decode(data: &[u8]) -> Self {
+        if let Ok(curve) = decode(data) {
+            return Self::Current(curve);
+        }
+        // Legacy curves share the current discriminator
+        match data.get(8..).map(|mut body| BondingCurveV0::deserialize(&mut body)) {
+            Some(Ok(curve)) => Self::Legacy(curve),
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Expand the cluster shorthands the Solana CLI accepts
+fn rpc_url(url: &str) -> &str {
+    match url {
+        "localnet" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "mainnet" | "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+}
+
+pub fn run(cli: &Cli) -> Result<Value> {
+    let cluster = Cluster {
+        rpc: RpcClient::new_with_commitment(rpc_url(&cli.url), CommitmentConfig::confirmed()),
+        keypair: cli.keypair.clone(),
+    };
+
+    match &cli.command {
+        Command::InitPlatform { fee_wallet } => {
+            let signer = cluster.signer()?;
+            let ix = instructions::initialize_platform(&signer.pubkey(), fee_wallet);
+            let signature = cluster.send(&[ix], &signer)?;
+            Ok(json!({
+                "signature": signature.to_string(),
+                "platform_state": pda::platform_state().to_string(),
+                "authority": signer.pubkey().to_string(),
+                "platform_fee_wallet": fee_wallet.to_string(),
+            }))
+        }
+
+        Command::CreateToken {
+            name,
+            symbol,
+            uri,
+            twitter,
+            twitch,
+            freeze_creator_allocation,
+            launch_protection_slots,
+            launch_max_buy_lamports,
+            sniper_fee_bps,
+            max_holding_bps,
+            initial_buy_lamports,
+        } => {
+            let signer = cluster.signer()?;
+            let platform = cluster.platform()?;
+            let args = CreateTokenArgs {
+                token_name: name.clone(),
+                token_symbol: symbol.clone(),
+                token_uri: uri.clone(),
+                creator_twitter: twitter.clone(),
+                creator_twitch: twitch.clone(),
+                freeze_creator_allocation: *freeze_creator_allocation,
+                launch_protection_slots: *launch_protection_slots,
+                launch_max_buy_lamports: *launch_max_buy_lamports,
+                sniper_fee_bps: *sniper_fee_bps,
+                max_holding_bps: *max_holding_bps,
+                initial_buy_lamports: *initial_buy_lamports,
+                fee_recipients: vec![],
+            };
+            let ix = instructions::create_token(&signer.pubkey(), &platform.platform_fee_wallet, args);
+            let signature = cluster.send(&[ix], &signer)?;
+            let token = TokenAccounts::new(&signer.pubkey());
+            Ok(json!({
+                "signature": signature.to_string(),
+                "mint": token.token_mint.to_string(),
+                "bonding_curve": token.bonding_curve.to_string(),
+                "creator": signer.pubkey().to_string(),
+            }))
+        }
+
+        Command::Buy { mint, amount, lamports, slippage_bps, referrer } => {
+            let signer = cluster.signer()?;
+            let platform = cluster.platform()?;
+            let (token, state) = cluster.token(mint)?;
+            let clock = cluster.clock()?;
+            let launch_sol_bought = cluster
+                .rpc
+                .get_account_data(&pda::buyer_record(&token.bonding_curve, &signer.pubkey()))
+                .ok()
+                .and_then(|data| decode::<BuyerRecord>(&data).ok())
+                .map_or(0, |record| record.launch_sol_bought);
+
+            let quote = match (amount, lamports) {
+                (Some(amount), _) => quote_buy(
+                    &state.curve,
+                    &state.reserves,
+                    &platform,
+                    *amount,
+                    clock.unix_timestamp,
+                    clock.slot,
+                    launch_sol_bought,
+                    referrer.is_some(),
+                ),
+                (None, Some(lamports)) => quote_buy_for_sol(
+                    &state.curve,
+                    &state.reserves,
+                    &platform,
+                    *lamports,
+                    clock.unix_timestamp,
+                    clock.slot,
+                    launch_sol_bought,
+                    referrer.is_some(),
+                ),
+                (None, None) => bail!("pass --amount or --lamports"),
+            }?;
+
+            let max_sol_cost = quote::with_buy_slippage(quote.total_cost, *slippage_bps);
+            let ix = instructions::buy_tokens(
+                &token,
+                &platform.platform_fee_wallet,
+                &signer.pubkey(),
+                quote.token_amount,
+                max_sol_cost,
+                referrer.as_ref(),
+            );
+            let signature = cluster.send(&[ix], &signer)?;
+            Ok(json!({
+                "signature": signature.to_string(),
+                "token_amount": quote.token_amount,
+                "sol_required": quote.sol_required,
+                "platform_fee": quote.platform_fee,
+                "referral_fee": quote.referral_fee,
+                "creator_fee": quote.creator_fee,
+                "holder_reward": quote.holder_reward,
+                "sniper_fee": quote.sniper_fee,
+                "quoted_total": quote.total_cost,
+                "max_sol_cost": max_sol_cost,
+            }))
+        }
+
+        Command::Sell { mint, amount, slippage_bps, referrer } => {
+            let signer = cluster.signer()?;
+            let platform = cluster.platform()?;
+            let (token, state) = cluster.token(mint)?;
+            let clock = cluster.clock()?;
+            let seller_stake = cluster
+                .rpc
+                .get_account_data(&pda::buyer_record(&token.bonding_curve, &signer.pubkey()))
+                .ok()
+                .and_then(|data| decode::<BuyerRecord>(&data).ok())
+                .map_or(0, |record| record.reward_balance);
+
+            let quote = quote_sell(
+                &state.curve,
+                &state.reserves,
+                &platform,
+                *amount,
+                clock.unix_timestamp,
+                seller_stake,
+                referrer.is_some(),
+            )?;
+            let min_sol_output = quote::with_sell_slippage(quote.net_output, *slippage_bps);
+            let ix = instructions::sell_tokens(
+                &token,
+                &platform.platform_fee_wallet,
+                &signer.pubkey(),
+                *amount,
+                min_sol_output,
+                referrer.as_ref(),
+            );
+            let signature = cluster.send(&[ix], &signer)?;
+            Ok(json!({
+                "signature": signature.to_string(),
+                "token_amount": quote.token_amount,
+                "sol_to_return": quote.sol_to_return,
+                "platform_fee": quote.platform_fee,
+                "referral_fee": quote.referral_fee,
+                "creator_fee": quote.creator_fee,
+                "holder_reward": quote.holder_reward,
+                "quoted_output": quote.net_output,
+                "min_sol_output": min_sol_output,
+            }))
+        }
+
+        Command::SetStream { mint, status, tier } => {
+            let signer = cluster.signer()?;
+            let (token, _) = cluster.token(mint)?;
+            let is_live = matches!(status, StreamStatus::On);
+            let mut ixs = Vec::new();
+            if let Some(tier) = tier {
+                ixs.push(instructions::update_stream_tier(&token, &signer.pubkey(), *tier));
+            }
+            ixs.push(instructions::update_stream_status(&token, &signer.pubkey(), is_live));
+            let signature = cluster.send(&ixs, &signer)?;
+            Ok(json!({
+                "signature": signature.to_string(),
+                "mint": mint.to_string(),
+                "live": is_live,
+                "tier": tier,
+            }))
+        }
+
+        Command::WithdrawFees { mint, amount, destination } => {
+            let signer = cluster.signer()?;
+            let (token, state) = cluster.token(mint)?;
+            let ix = instructions::withdraw_creator_fees(
+                &token,
+                &signer.pubkey(),
+                &state.curve.fee_recipients,
+                *amount,
+                destination.as_ref(),
+            );
+            let signature = cluster.send(&[ix], &signer)?;
+            let after = cluster.token_state(&token)?;
+            Ok(withdrawal_json(&signature, &state.reserves, &after.reserves))
+        }
+
+        Command::ShowCurve { mint } => {
+            let (token, state) = cluster.token(mint)?;
+            let platform = cluster.platform()?;
+            let clock = cluster.clock()?;
+            Ok(curve_json(&token, &state, &platform, &clock))
+        }
+
+        Command::ListTokens => {
+            let tokens = cluster
+                .curves(None)?
+                .into_iter()
+                .map(|(address, curve)| token_row(&address, curve, |token| cluster.token_state(token)))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Value::Array(tokens))
+        }
+    }
+}
+
+/// A withdraw-fees result, from what the program recorded rather than what
+/// was requested
+fn withdrawal_json(signature: &Signature, before: &CurveReserves, after: &CurveReserves) -> Value {
+    json!({
+        "signature": signature.to_string(),
+        "withdrawn": after.creator_fees_withdrawn.saturating_sub(before.creator_fees_withdrawn),
+        "pending_before": before.creator_fees_pending(),
+        "pending_after": after.creator_fees_pending(),
+    })
+}
+
+/// A list-tokens row; `token_state` fetches the accounts of a current curve
+fn token_row(
+    address: &Pubkey,
+    curve: Curve,
+    token_state: impl FnOnce(&TokenAccounts) -> Result<TokenState>,
+) -> Result<Value> {
+    let curve = match curve {
+        Curve::Current(curve) => curve,
+        Curve::Legacy(curve) => {
+            return Ok(json!({
+                "mint": curve.token_mint.to_string(),
+                "symbol": curve.token_symbol,
+                "name": curve.token_name,
+                "creator": curve.creator.to_string(),
+                "status": "needs migration",
+            }));
+        }
+        Curve::Unknown => {
+            return Ok(json!({
+                "bonding_curve": address.to_string(),
+                "status": "undecodable",
+            }));
+        }
+    };
+    let token = TokenAccounts::new(&curve.creator);
+    let state = token_state(&token)?;
+    Ok(json!({
+        "mint": token.token_mint.to_string(),
+        "symbol": state.metadata.token_symbol,
+        "name": state.metadata.token_name,
+        "creator": token.creator.to_string(),
+        "live": state.curve.is_live_streaming,
+        "graduated": state.reserves.is_graduated(),
+        "tokens_sold": state.reserves.tokens_sold,
+        "real_sol_reserves": state.reserves.real_sol_reserves,
+    }))
+}
+
+fn curve_json(token: &TokenAccounts, state: &TokenState, platform: &PlatformState, clock: &Clock) -> Value {
+    let TokenState { curve, reserves, metadata } = state;
+    json!({
+        "mint": token.token_mint.to_string(),
+        "name": metadata.token_name,
+        "symbol": metadata.token_symbol,
+        "uri": metadata.token_uri,
+        "creator": curve.creator.to_string(),
+        "fee_authority": curve.fee_authority.to_string(),
+        "bonding_curve": token.bonding_curve.to_string(),
+        "stream": {
+            "live": curve.is_live_at(clock.unix_timestamp, platform.max_stream_staleness_secs),
+            "reported_live": curve.is_live_streaming,
+            "last_stream_check": curve.last_stream_check,
+            "live_fee_tier": curve.live_fee_tier,
+            "creator_fee_bps": curve.creator_fee_bps_at(clock.unix_timestamp, platform),
+        },
+        "reserves": {
+            "virtual_sol": reserves.virtual_sol_reserves,
+            "virtual_token": reserves.virtual_token_reserves,
+            "real_sol": reserves.real_sol_reserves,
+            "real_token": reserves.real_token_reserves,
+        },
+        "tokens_sold": reserves.tokens_sold,
+        "curve_supply": CURVE_SUPPLY,
+        "price_lamports": reserves.virtual_sol_reserves as f64 / reserves.virtual_token_reserves as f64,
+        "progress_pct": reserves.get_progress(),
+        "graduated": reserves.is_graduated(),
+        "total_volume": reserves.total_volume,
+        "creator_fees_pending": reserves.creator_fees_pending(),
+        "holder_rewards_distributed": reserves.holder_rewards_distributed,
+        "fee_recipients": curve.fee_recipients.iter().map(|recipient| json!({
+            "wallet": recipient.wallet.to_string(),
+            "weight_bps": recipient.weight_bps,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, AnchorSerialize, Space};
+    use bytemuck::Zeroable;
+    use zeroglaze_client::accounts::{StreamHistory, TokenMetadata};
+    use zeroglaze_client::zeroglaze::constants::STREAM_HISTORY_LEN;
+    use zeroglaze_client::zeroglaze::state::StreamSession;
+
+    fn v0_curve(creator: Pubkey) -> BondingCurveV0 {
+        BondingCurveV0 {
+            creator,
+            token_mint: pda::token_mint(&creator),
+            token_name: "Legacy Token".to_string(),
+            token_symbol: "OLD".to_string(),
+            token_uri: "https://example.com/legacy.json".to_string(),
+            creator_twitter: "@legacy".to_string(),
+            creator_twitch: "legacy_streams".to_string(),
+            freeze_creator_allocation: false,
+            virtual_sol_reserves: 31_000_000_000,
+            virtual_token_reserves: 1_063_000_000_000_000,
+            real_sol_reserves: 1_000_000_000,
+            real_token_reserves: 760_000_000_000_000,
+            tokens_sold: 33_000_000_000_000,
+            total_volume: 1_000_000_000,
+            creator_fees_collected: 10_000_000,
+            is_live_streaming: false,
+            last_stream_check: 1_700_000_000,
+            graduated: false,
+            created_at: 1_700_000_000,
+            bump: 255,
+        }
+    }
+
+    /// Account data as the program allocated it: discriminator, fields, zero padding
+    fn v0_account_data(curve: &BondingCurveV0) -> Vec<u8> {
+        let mut data = BondingCurve::DISCRIMINATOR.to_vec();
+        curve.serialize(&mut data).unwrap();
+        data.resize(8 + BondingCurveV0::INIT_SPACE, 0);
+        data
+    }
+
+    /// The state migrate_curve leaves behind for `v0`
+    fn migrated(v0: BondingCurveV0) -> TokenState {
+        let token = TokenAccounts::new(&v0.creator);
+        let mut reserves = CurveReserves { bonding_curve: token.bonding_curve, ..CurveReserves::zeroed() };
+        let mut metadata = TokenMetadata {
+            bonding_curve: token.bonding_curve,
+            token_name: String::new(),
+            token_symbol: String::new(),
+            token_uri: String::new(),
+            creator_twitter: String::new(),
+            creator_twitch: String::new(),
+            bump: 0,
+        };
+        let mut stream_history = StreamHistory {
+            bonding_curve: token.bonding_curve,
+            total_live_secs: 0,
+            session_count: 0,
+            current_session_start: 0,
+            sessions: [StreamSession::default(); STREAM_HISTORY_LEN],
+            next_session_index: 0,
+            bump: 0,
+        };
+        let curve = v0.split_into(&mut reserves, &mut metadata, &mut stream_history);
+        TokenState { curve, reserves, metadata }
+    }
+
+    #[test]
+    fn legacy_curve_is_listed_for_migration() {
+        let creator = Pubkey::new_unique();
+        let curve = Curve::decode(&v0_account_data(&v0_curve(creator)));
+        assert!(matches!(curve, Curve::Legacy(_)));
+
+        let address = pda::bonding_curve(&creator, &pda::token_mint(&creator));
+        let row = token_row(&address, curve, |_| panic!("legacy curves have no accounts to fetch")).unwrap();
+        assert_eq!(row["status"], "needs migration");
+        assert_eq!(row["mint"], pda::token_mint(&creator).to_string());
+        assert_eq!(row["creator"], creator.to_string());
+        assert_eq!(row["symbol"], "OLD");
+        assert_eq!(row["name"], "Legacy Token");
+    }
+
+    #[test]
+    fn current_curve_is_listed_from_its_accounts() {
+        let creator = Pubkey::new_unique();
+        let state = migrated(v0_curve(creator));
+        let mut data = Vec::new();
+        state.curve.try_serialize(&mut data).unwrap();
+        data.resize(8 + BondingCurve::INIT_SPACE, 0);
+        let curve = Curve::decode(&data);
+        assert!(matches!(curve, Curve::Current(_)));
+
+        let address = pda::bonding_curve(&creator, &pda::token_mint(&creator));
+        let row = token_row(&address, curve, |token| {
+            assert_eq!(token.bonding_curve, address);
+            Ok(state.clone())
+        })
+        .unwrap();
+        assert!(row.get("status").is_none());
+        assert_eq!(row["mint"], pda::token_mint(&creator).to_string());
+        assert_eq!(row["symbol"], "OLD");
+        assert_eq!(row["graduated"], false);
+        assert_eq!(row["tokens_sold"], 33_000_000_000_000u64);
+        assert_eq!(row["real_sol_reserves"], 1_000_000_000u64);
+    }
+
+    #[test]
+    fn truncated_curve_is_listed_as_undecodable() {
+        let mut data = v0_account_data(&v0_curve(Pubkey::new_unique()));
+        data.truncate(40);
+        let curve = Curve::decode(&data);
+        assert!(matches!(curve, Curve::Unknown));
+
+        let address = Pubkey::new_unique();
+        let row = token_row(&address, curve, |_| panic!("nothing to fetch")).unwrap();
+        assert_eq!(row["status"], "undecodable");
+        assert_eq!(row["bonding_curve"], address.to_string());
+    }
+
+    #[test]
+    fn withdrawal_reports_the_recorded_change() {
+        let before = CurveReserves {
+            creator_fees_collected: 5_000_000,
+            creator_fees_withdrawn: 1_000_000,
+            ..CurveReserves::zeroed()
+        };
+        // A partial withdrawal of 2_500_000
+        let after = CurveReserves { creator_fees_withdrawn: 3_500_000, ..before };
+        let result = withdrawal_json(&Signature::default(), &before, &after);
+        assert_eq!(result["withdrawn"], 2_500_000);
+        assert_eq!(result["pending_before"], 4_000_000);
+        assert_eq!(result["pending_after"], 1_500_000);
+
+        // Fees collected between the two reads do not count as withdrawn
+        let after = CurveReserves { creator_fees_collected: 6_000_000, ..after };
+        let result = withdrawal_json(&Signature::default(), &before, &after);
+        assert_eq!(result["withdrawn"], 2_500_000);
+        assert_eq!(result["pending_after"], 2_500_000);
+    }
+}
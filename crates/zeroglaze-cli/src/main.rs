@@ -0,0 +1,159 @@
+//! Admin and trading tool for the Zeroglaze program
+//!
+//! Token amounts are raw base units and SOL amounts are lamports, exactly as
+//! the program takes them. Tokens are identified by their mint address.
+
+mod commands;
+mod output;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Parser)]
+#[command(name = "zeroglaze-cli", version, about)]
+pub struct Cli {
+    /// RPC URL, or one of localnet / devnet / mainnet
+    #[arg(long, short = 'u', global = true, env = "ZEROGLAZE_RPC_URL", default_value = "localnet")]
+    pub url: String,
+
+    /// Keypair that signs and pays for transactions
+    #[arg(long, short = 'k', global = true, env = "ZEROGLAZE_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create the platform state with the signer as authority
+    InitPlatform {
+        /// Wallet that receives platform fees
+        #[arg(long)]
+        fee_wallet: Pubkey,
+    },
+
+    /// Launch a token with the signer as creator
+    CreateToken {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long)]
+        uri: String,
+        #[arg(long, default_value = "")]
+        twitter: String,
+        #[arg(long, default_value = "")]
+        twitch: String,
+        /// Freeze the creator allocation in the creator's token account
+        #[arg(long)]
+        freeze_creator_allocation: bool,
+        #[arg(long, default_value_t = 0)]
+        launch_protection_slots: u64,
+        #[arg(long, default_value_t = 0)]
+        launch_max_buy_lamports: u64,
+        #[arg(long, default_value_t = 0)]
+        sniper_fee_bps: u16,
+        #[arg(long, default_value_t = 0)]
+        max_holding_bps: u16,
+        /// Lamports the creator spends on the curve at launch
+        #[arg(long)]
+        initial_buy_lamports: Option<u64>,
+    },
+
+    /// Buy tokens from the curve
+    Buy {
+        mint: Pubkey,
+        /// Exact token amount to buy
+        #[arg(long, conflicts_with = "lamports", required_unless_present = "lamports")]
+        amount: Option<u64>,
+        /// Lamports to spend on the curve, before fees
+        #[arg(long)]
+        lamports: Option<u64>,
+        /// Extra cost tolerated over the quote
+        #[arg(long, default_value_t = 100)]
+        slippage_bps: u16,
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+
+    /// Sell tokens back to the curve
+    Sell {
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Shortfall tolerated under the quote
+        #[arg(long, default_value_t = 100)]
+        slippage_bps: u16,
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+
+    /// Set a token's stream status (platform authority only)
+    SetStream {
+        mint: Pubkey,
+        status: StreamStatus,
+        /// Live fee tier to apply
+        #[arg(long)]
+        tier: Option<u8>,
+    },
+
+    /// Withdraw pending creator fees (fee authority only)
+    WithdrawFees {
+        mint: Pubkey,
+        /// Lamports to withdraw; everything pending if omitted
+        #[arg(long)]
+        amount: Option<u64>,
+        /// Where the fee authority's share goes; the signer if omitted
+        #[arg(long)]
+        destination: Option<Pubkey>,
+    },
+
+    /// Show a token's curve, reserves and stream status
+    ShowCurve { mint: Pubkey },
+
+    /// List every token launched on the platform
+    ListTokens,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StreamStatus {
+    On,
+    Off,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let value = commands::run(&cli)?;
+    output::print(cli.json, &value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_is_well_formed() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn buy_needs_exactly_one_amount() {
+        let mint = Pubkey::new_unique().to_string();
+        assert!(Cli::try_parse_from(["zeroglaze-cli", "buy", &mint]).is_err());
+        assert!(Cli::try_parse_from(["zeroglaze-cli", "buy", &mint, "--amount", "1", "--lamports", "1"]).is_err());
+
+        let cli = Cli::try_parse_from(["zeroglaze-cli", "--json", "buy", &mint, "--lamports", "5"]).unwrap();
+        assert!(cli.json);
+        assert!(matches!(cli.command, Command::Buy { lamports: Some(5), amount: None, .. }));
+    }
+}
@@ -0,0 +1,49 @@
+use serde_json::{Map, Value};
+
+/// Print a command's result, as JSON or as aligned `key  value` lines
+pub fn print(json: bool, value: &Value) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value).expect("value serializes"));
+        return;
+    }
+    match value {
+        Value::Array(rows) => {
+            for (i, row) in rows.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print_object(row.as_object().expect("rows are objects"), "");
+            }
+        }
+        Value::Object(object) => print_object(object, ""),
+        other => println!("{}", plain(other)),
+    }
+}
+
+fn print_object(object: &Map<String, Value>, indent: &str) {
+    let width = object.keys().map(String::len).max().unwrap_or(0);
+    for (key, value) in object {
+        match value {
+            Value::Object(nested) => {
+                println!("{indent}{key}:");
+                print_object(nested, &format!("{indent}  "));
+            }
+            Value::Array(items) if items.iter().any(Value::is_object) => {
+                println!("{indent}{key}:");
+                for item in items {
+                    print_object(item.as_object().expect("items are objects"), &format!("{indent}  "));
+                }
+            }
+            _ => println!("{indent}{key:width$}  {}", plain(value)),
+        }
+    }
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "-".to_string(),
+        Value::Array(items) => items.iter().map(plain).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::{AccountDeserialize, Discriminator, Result};
+
+pub use zeroglaze::state::{
+    BondingCurve, BuyerRecord, CurveReserves, GraduatedToken, PlatformState, ReferrerStats,
+    StreamHistory, TokenMetadata,
+};
+
+/// Decode account data as fetched over RPC, checking the discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Whether `data` starts with `T`'s discriminator, e.g. to pick one account
+/// type out of `getProgramAccounts` results
+pub fn is_account<T: Discriminator>(data: &[u8]) -> bool {
+    data.get(..8) == Some(&T::DISCRIMINATOR[..])
+}
+
+/// A token's curve, reserves and metadata accounts
+#[derive(Clone)]
+pub struct TokenState {
+    pub curve: BondingCurve,
+    pub reserves: CurveReserves,
+    pub metadata: TokenMetadata,
+}
+
+impl TokenState {
+    pub fn decode(curve: &[u8], reserves: &[u8], metadata: &[u8]) -> Result<Self> {
+        Ok(Self {
+            curve: decode(curve)?,
+            reserves: decode(reserves)?,
+            metadata: decode(metadata)?,
+        })
+    }
+}
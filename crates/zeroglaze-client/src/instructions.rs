@@ -0,0 +1,450 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{ed25519_program, system_program, sysvar};
+use anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas};
+use zeroglaze::constants::LIVE_FEE_TIER_COUNT;
+use zeroglaze::state::{FeeRecipient, StreamAttestation};
+
+use crate::pda::{self, TokenAccounts};
+
+pub use zeroglaze::instruction::CreateToken as CreateTokenArgs;
+
+fn instruction(accounts: Vec<AccountMeta>, data: impl InstructionData) -> Instruction {
+    Instruction { program_id: zeroglaze::ID, accounts, data: data.data() }
+}
+
+// ============================================================================
+// Platform
+// ============================================================================
+
+pub fn initialize_platform(authority: &Pubkey, platform_fee_wallet: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::InitializePlatform {
+            platform_state: pda::platform_state(),
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::InitializePlatform { platform_fee_wallet: *platform_fee_wallet },
+    )
+}
+
+fn platform_config(authority: &Pubkey, data: impl InstructionData) -> Instruction {
+    instruction(
+        zeroglaze::accounts::UpdatePlatformConfig {
+            platform_state: pda::platform_state(),
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data,
+    )
+}
+
+pub fn set_max_stream_staleness(authority: &Pubkey, max_stream_staleness_secs: i64) -> Instruction {
+    platform_config(
+        authority,
+        zeroglaze::instruction::SetMaxStreamStaleness { max_stream_staleness_secs },
+    )
+}
+
+pub fn set_live_fee_tiers(
+    authority: &Pubkey,
+    live_fee_tiers_bps: [u16; LIVE_FEE_TIER_COUNT],
+) -> Instruction {
+    platform_config(authority, zeroglaze::instruction::SetLiveFeeTiers { live_fee_tiers_bps })
+}
+
+pub fn set_holder_reward_share(authority: &Pubkey, holder_reward_share_bps: u16) -> Instruction {
+    platform_config(
+        authority,
+        zeroglaze::instruction::SetHolderRewardShare { holder_reward_share_bps },
+    )
+}
+
+pub fn set_referral_fee(authority: &Pubkey, referral_fee_bps: u16) -> Instruction {
+    platform_config(authority, zeroglaze::instruction::SetReferralFee { referral_fee_bps })
+}
+
+pub fn set_stream_oracle(authority: &Pubkey, stream_oracle: &Pubkey) -> Instruction {
+    platform_config(
+        authority,
+        zeroglaze::instruction::SetStreamOracle { stream_oracle: *stream_oracle },
+    )
+}
+
+pub fn migrate_platform(authority: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::MigratePlatform {
+            platform_state: pda::platform_state(),
+            authority: *authority,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::MigratePlatform {},
+    )
+}
+
+// ============================================================================
+// Launch and trading
+// ============================================================================
+
+/// `platform_fee_wallet` must match the one recorded in `PlatformState`
+pub fn create_token(
+    creator: &Pubkey,
+    platform_fee_wallet: &Pubkey,
+    args: CreateTokenArgs,
+) -> Instruction {
+    let token = TokenAccounts::new(creator);
+    instruction(
+        zeroglaze::accounts::CreateToken {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            token_metadata: token.token_metadata,
+            stream_history: token.stream_history,
+            token_mint: token.token_mint,
+            curve_token_account: token.curve_token_account,
+            creator_token_account: token.token_account(creator),
+            curve_sol_vault: token.curve_sol_vault,
+            creator_fee_wallet: token.creator_fee_wallet,
+            holder_rewards_pool: token.holder_rewards_pool,
+            platform_fee_wallet: *platform_fee_wallet,
+            platform_state: pda::platform_state(),
+            creator: *creator,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        args,
+    )
+}
+
+/// Buy exactly `token_amount` tokens, paying at most `max_sol_cost` lamports
+/// including fees
+pub fn buy_tokens(
+    token: &TokenAccounts,
+    platform_fee_wallet: &Pubkey,
+    buyer: &Pubkey,
+    token_amount: u64,
+    max_sol_cost: u64,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    instruction(
+        zeroglaze::accounts::BuyTokens {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            token_mint: token.token_mint,
+            curve_token_account: token.curve_token_account,
+            buyer_token_account: token.token_account(buyer),
+            buyer_record: pda::buyer_record(&token.bonding_curve, buyer),
+            curve_sol_vault: token.curve_sol_vault,
+            platform_fee_wallet: *platform_fee_wallet,
+            creator_fee_wallet: token.creator_fee_wallet,
+            holder_rewards_pool: token.holder_rewards_pool,
+            platform_state: pda::platform_state(),
+            referrer: referrer.copied(),
+            referrer_stats: referrer.map(pda::referrer_stats),
+            buyer: *buyer,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::BuyTokens { token_amount, max_sol_cost },
+    )
+}
+
+/// Sell `token_amount` tokens, receiving at least `min_sol_output` lamports
+/// after fees
+pub fn sell_tokens(
+    token: &TokenAccounts,
+    platform_fee_wallet: &Pubkey,
+    seller: &Pubkey,
+    token_amount: u64,
+    min_sol_output: u64,
+    referrer: Option<&Pubkey>,
+) -> Instruction {
+    instruction(
+        zeroglaze::accounts::SellTokens {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            token_mint: token.token_mint,
+            curve_token_account: token.curve_token_account,
+            seller_token_account: token.token_account(seller),
+            seller_record: pda::buyer_record(&token.bonding_curve, seller),
+            curve_sol_vault: token.curve_sol_vault,
+            platform_fee_wallet: *platform_fee_wallet,
+            creator_fee_wallet: token.creator_fee_wallet,
+            holder_rewards_pool: token.holder_rewards_pool,
+            platform_state: pda::platform_state(),
+            referrer: referrer.copied(),
+            referrer_stats: referrer.map(pda::referrer_stats),
+            seller: *seller,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::SellTokens { token_amount, min_sol_output },
+    )
+}
+
+pub fn register_referrer(referrer: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::RegisterReferrer {
+            referrer_stats: pda::referrer_stats(referrer),
+            referrer: *referrer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::RegisterReferrer {},
+    )
+}
+
+pub fn claim_holder_rewards(token: &TokenAccounts, holder: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::ClaimHolderRewards {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            holder_record: pda::buyer_record(&token.bonding_curve, holder),
+            holder_token_account: token.token_account(holder),
+            holder_rewards_pool: token.holder_rewards_pool,
+            holder: *holder,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::ClaimHolderRewards {},
+    )
+}
+
+// ============================================================================
+// Stream status
+// ============================================================================
+
+fn stream_status(token: &TokenAccounts, authority: &Pubkey, data: impl InstructionData) -> Instruction {
+    instruction(
+        zeroglaze::accounts::UpdateStreamStatus {
+            bonding_curve: token.bonding_curve,
+            stream_history: token.stream_history,
+            authority: *authority,
+            platform_state: pda::platform_state(),
+        }
+        .to_account_metas(None),
+        data,
+    )
+}
+
+pub fn update_stream_status(token: &TokenAccounts, authority: &Pubkey, is_live: bool) -> Instruction {
+    stream_status(token, authority, zeroglaze::instruction::UpdateStreamStatus { is_live })
+}
+
+pub fn update_stream_tier(token: &TokenAccounts, authority: &Pubkey, live_fee_tier: u8) -> Instruction {
+    stream_status(token, authority, zeroglaze::instruction::UpdateStreamTier { live_fee_tier })
+}
+
+pub fn stream_heartbeat(token: &TokenAccounts, authority: &Pubkey) -> Instruction {
+    stream_status(token, authority, zeroglaze::instruction::StreamHeartbeat {})
+}
+
+/// Update several tokens at once; `statuses[i]` applies to `tokens[i]`
+pub fn batch_update_stream_status(
+    tokens: &[TokenAccounts],
+    authority: &Pubkey,
+    statuses: Vec<bool>,
+) -> Instruction {
+    let mut accounts = zeroglaze::accounts::BatchUpdateStreamStatus {
+        authority: *authority,
+        platform_state: pda::platform_state(),
+    }
+    .to_account_metas(None);
+    accounts.extend(tokens.iter().flat_map(|token| {
+        [
+            AccountMeta::new(token.bonding_curve, false),
+            AccountMeta::new(token.stream_history, false),
+        ]
+    }));
+    instruction(accounts, zeroglaze::instruction::BatchUpdateStreamStatus { statuses })
+}
+
+/// Bytes the stream oracle signs for an attestation
+pub fn attestation_message(attestation: &StreamAttestation) -> Vec<u8> {
+    attestation.try_to_vec().expect("attestation serializes")
+}
+
+/// Ed25519 program instruction checking `oracle`'s `signature` over
+/// `message`, with everything stored inline as the program expects
+pub fn ed25519_verify(oracle: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    let pubkey_offset: u16 = 16;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        pubkey_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(oracle.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+}
+
+/// Relay an oracle attestation; must directly follow the matching
+/// [`ed25519_verify`] instruction in the same transaction
+pub fn relay_stream_attestation(token: &TokenAccounts, attestation: StreamAttestation) -> Instruction {
+    instruction(
+        zeroglaze::accounts::RelayStreamAttestation {
+            bonding_curve: token.bonding_curve,
+            stream_history: token.stream_history,
+            platform_state: pda::platform_state(),
+            instructions: sysvar::instructions::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::RelayStreamAttestation { attestation },
+    )
+}
+
+// ============================================================================
+// Creator fees and ownership
+// ============================================================================
+
+/// Withdraw creator fees; `recipients` must be the curve's current
+/// `fee_recipients`. `amount: None` withdraws everything pending.
+pub fn withdraw_creator_fees(
+    token: &TokenAccounts,
+    fee_authority: &Pubkey,
+    recipients: &[FeeRecipient],
+    amount: Option<u64>,
+    destination: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = zeroglaze::accounts::WithdrawCreatorFees {
+        bonding_curve: token.bonding_curve,
+        curve_reserves: token.curve_reserves,
+        creator_fee_wallet: token.creator_fee_wallet,
+        fee_authority: *fee_authority,
+        destination: destination.copied(),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(recipients.iter().map(|recipient| AccountMeta::new(recipient.wallet, false)));
+    instruction(accounts, zeroglaze::instruction::WithdrawCreatorFees { amount })
+}
+
+/// Replace the fee split; existing recipients whose share shrinks must
+/// co-sign, passed in `cosigners`
+pub fn update_fee_recipients(
+    token: &TokenAccounts,
+    fee_authority: &Pubkey,
+    fee_recipients: Vec<FeeRecipient>,
+    cosigners: &[Pubkey],
+) -> Instruction {
+    let mut accounts = zeroglaze::accounts::UpdateFeeRecipients {
+        bonding_curve: token.bonding_curve,
+        fee_authority: *fee_authority,
+    }
+    .to_account_metas(None);
+    accounts.extend(cosigners.iter().map(|cosigner| AccountMeta::new_readonly(*cosigner, true)));
+    instruction(accounts, zeroglaze::instruction::UpdateFeeRecipients { fee_recipients })
+}
+
+pub fn transfer_creator(token: &TokenAccounts, fee_authority: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::TransferCreator {
+            bonding_curve: token.bonding_curve,
+            fee_authority: *fee_authority,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::TransferCreator { new_authority: *new_authority },
+    )
+}
+
+pub fn accept_creator(token: &TokenAccounts, new_authority: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::AcceptCreator {
+            bonding_curve: token.bonding_curve,
+            new_authority: *new_authority,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::AcceptCreator {},
+    )
+}
+
+/// Move a graduated curve's liquidity to its `dex_liquidity` PDA, creating
+/// the PDA's token account if needed
+pub fn withdraw_graduated_liquidity(
+    token: &TokenAccounts,
+    authority: &Pubkey,
+    platform_fee_wallet: &Pubkey,
+) -> Instruction {
+    let dex_liquidity = pda::dex_liquidity(&token.token_mint);
+    instruction(
+        zeroglaze::accounts::WithdrawGraduatedLiquidity {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            curve_token_account: token.curve_token_account,
+            curve_sol_vault: token.curve_sol_vault,
+            dex_liquidity,
+            dex_liquidity_token_account: token.token_account(&dex_liquidity),
+            token_mint: token.token_mint,
+            platform_fee_wallet: *platform_fee_wallet,
+            platform_state: pda::platform_state(),
+            authority: *authority,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::WithdrawGraduatedLiquidity {},
+    )
+}
+
+pub fn close_curve(token: &TokenAccounts, fee_authority: &Pubkey, platform_fee_wallet: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::CloseCurve {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            token_metadata: token.token_metadata,
+            stream_history: token.stream_history,
+            graduated_token: pda::graduated_token(&token.token_mint),
+            curve_token_account: token.curve_token_account,
+            curve_sol_vault: token.curve_sol_vault,
+            creator_fee_wallet: token.creator_fee_wallet,
+            holder_rewards_pool: token.holder_rewards_pool,
+            creator: token.creator,
+            platform_fee_wallet: *platform_fee_wallet,
+            platform_state: pda::platform_state(),
+            fee_authority: *fee_authority,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::CloseCurve {},
+    )
+}
+
+/// Migrate a pre-versioning curve; anyone can pay for the new accounts
+pub fn migrate_curve(token: &TokenAccounts, payer: &Pubkey) -> Instruction {
+    instruction(
+        zeroglaze::accounts::MigrateCurve {
+            bonding_curve: token.bonding_curve,
+            curve_reserves: token.curve_reserves,
+            token_metadata: token.token_metadata,
+            stream_history: token.stream_history,
+            curve_sol_vault: token.curve_sol_vault,
+            creator_fee_wallet: token.creator_fee_wallet,
+            holder_rewards_pool: token.holder_rewards_pool,
+            payer: *payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        zeroglaze::instruction::MigrateCurve {},
+    )
+}
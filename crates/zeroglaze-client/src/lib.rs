@@ -0,0 +1,18 @@
+//! Client-side helpers for the Zeroglaze program
+//!
+//! - [`pda`]: account addresses, derived with the program's seeds
+//! - [`instructions`]: a builder for every program instruction
+//! - [`accounts`]: decoding fetched account data
+//! - [`quote`]: buy and sell quotes matching what the program charges
+//!
+//! Builders only assemble instructions; signing and sending is left to the
+//! caller's RPC client.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use pda::TokenAccounts;
+pub use zeroglaze;
+pub use zeroglaze::ID as PROGRAM_ID;
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+
+pub fn platform_state() -> Pubkey {
+    Pubkey::find_program_address(&[b"platform_state"], &zeroglaze::ID).0
+}
+
+/// Each creator has a single token, so the mint is derived from the creator alone
+pub fn token_mint(creator: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_mint", creator.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn bonding_curve(creator: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"bonding_curve", creator.as_ref(), token_mint.as_ref()],
+        &zeroglaze::ID,
+    )
+    .0
+}
+
+pub fn curve_reserves(bonding_curve: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"curve_reserves", bonding_curve.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn token_metadata(bonding_curve: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_metadata", bonding_curve.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn curve_sol_vault(bonding_curve: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"curve_sol_vault", bonding_curve.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn creator_fee_wallet(creator: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"creator_fee_wallet", creator.as_ref(), token_mint.as_ref()],
+        &zeroglaze::ID,
+    )
+    .0
+}
+
+pub fn holder_rewards_pool(bonding_curve: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"holder_rewards_pool", bonding_curve.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn stream_history(bonding_curve: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"stream_history", bonding_curve.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn buyer_record(bonding_curve: &Pubkey, buyer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"buyer_record", bonding_curve.as_ref(), buyer.as_ref()],
+        &zeroglaze::ID,
+    )
+    .0
+}
+
+pub fn referrer_stats(referrer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"referrer_stats", referrer.as_ref()], &zeroglaze::ID).0
+}
+
+/// Holds a graduated token's DEX liquidity until the pool is seeded
+pub fn dex_liquidity(token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"dex_liquidity", token_mint.as_ref()], &zeroglaze::ID).0
+}
+
+pub fn graduated_token(token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"graduated_token", token_mint.as_ref()], &zeroglaze::ID).0
+}
+
+/// Addresses of every account belonging to a launched token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenAccounts {
+    pub creator: Pubkey,
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub curve_reserves: Pubkey,
+    pub token_metadata: Pubkey,
+    pub stream_history: Pubkey,
+    pub curve_token_account: Pubkey,
+    pub curve_sol_vault: Pubkey,
+    pub creator_fee_wallet: Pubkey,
+    pub holder_rewards_pool: Pubkey,
+}
+
+impl TokenAccounts {
+    pub fn new(creator: &Pubkey) -> Self {
+        let token_mint = token_mint(creator);
+        let bonding_curve = bonding_curve(creator, &token_mint);
+        Self {
+            creator: *creator,
+            token_mint,
+            bonding_curve,
+            curve_reserves: curve_reserves(&bonding_curve),
+            token_metadata: token_metadata(&bonding_curve),
+            stream_history: stream_history(&bonding_curve),
+            curve_token_account: get_associated_token_address(&bonding_curve, &token_mint),
+            curve_sol_vault: curve_sol_vault(&bonding_curve),
+            creator_fee_wallet: creator_fee_wallet(creator, &token_mint),
+            holder_rewards_pool: holder_rewards_pool(&bonding_curve),
+        }
+    }
+
+    /// A wallet's associated token account for this token
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.token_mint)
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use zeroglaze::state::{BondingCurve, CurveReserves, PlatformState};
+use zeroglaze::trade::{compute_buy, compute_sell};
+use zeroglaze::utils::{calculate_fee, calculate_tokens_for_sol};
+
+pub use zeroglaze::trade::{BuyQuote, SellQuote};
+
+/// Quote buying `token_amount` tokens at `now` / `slot`
+///
+/// `launch_sol_bought` is the buyer's `BuyerRecord::launch_sol_bought` (0 if
+/// they have no record); it only matters during launch protection.
+/// `referred` is whether the buy passes a valid referrer.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_buy(
+    curve: &BondingCurve,
+    reserves: &CurveReserves,
+    platform: &PlatformState,
+    token_amount: u64,
+    now: i64,
+    slot: u64,
+    launch_sol_bought: u64,
+    referred: bool,
+) -> Result<BuyQuote> {
+    compute_buy(curve, reserves, platform, token_amount, now, slot, launch_sol_bought, referred)
+}
+
+/// Quote spending `lamports` on the curve (fees come on top)
+#[allow(clippy::too_many_arguments)]
+pub fn quote_buy_for_sol(
+    curve: &BondingCurve,
+    reserves: &CurveReserves,
+    platform: &PlatformState,
+    lamports: u64,
+    now: i64,
+    slot: u64,
+    launch_sol_bought: u64,
+    referred: bool,
+) -> Result<BuyQuote> {
+    let token_amount = calculate_tokens_for_sol(
+        reserves.virtual_sol_reserves,
+        reserves.virtual_token_reserves,
+        lamports,
+    )?;
+    quote_buy(curve, reserves, platform, token_amount, now, slot, launch_sol_bought, referred)
+}
+
+/// Quote selling `token_amount` tokens at `now`
+///
+/// `seller_stake` is the seller's `BuyerRecord::reward_balance`; the live
+/// bonus on a sell goes to the holders that remain.
+pub fn quote_sell(
+    curve: &BondingCurve,
+    reserves: &CurveReserves,
+    platform: &PlatformState,
+    token_amount: u64,
+    now: i64,
+    seller_stake: u64,
+    referred: bool,
+) -> Result<SellQuote> {
+    let mut remaining = *reserves;
+    remaining.reward_eligible_supply = remaining
+        .reward_eligible_supply
+        .saturating_sub(token_amount.min(seller_stake));
+    compute_sell(curve, &remaining, platform, token_amount, now, referred)
+}
+
+/// Apply `slippage_bps` to a buy's total cost, for `max_sol_cost`
+pub fn with_buy_slippage(total_cost: u64, slippage_bps: u16) -> u64 {
+    total_cost.saturating_add(calculate_fee(total_cost, slippage_bps).unwrap_or(u64::MAX))
+}
+
+/// Apply `slippage_bps` to a sell's output, for `min_sol_output`
+pub fn with_sell_slippage(net_output: u64, slippage_bps: u16) -> u64 {
+    net_output.saturating_sub(calculate_fee(net_output, slippage_bps).unwrap_or(net_output))
+}
@@ -0,0 +1,220 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::state::{BondingCurve, BuyerRecord, CurveReserves, TokenMetadata};
+use zeroglaze_client::accounts::{decode, is_account};
+use zeroglaze_client::{instructions, pda};
+use zeroglaze_client::quote::{quote_buy, quote_buy_for_sol, quote_sell};
+use zeroglaze_test_harness::TestEnv;
+
+#[tokio::test]
+async fn pdas_match_created_accounts() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+
+    let platform = env.platform_state().await;
+    assert_eq!(platform.authority, env.payer().pubkey());
+    assert_eq!(platform.platform_fee_wallet, env.platform_fee_wallet.pubkey());
+
+    let state = env.token_state(&token).await;
+    assert_eq!(state.curve.creator, creator.pubkey());
+    assert_eq!(state.curve.token_mint, pda::token_mint(&creator.pubkey()));
+    assert_eq!(state.reserves.bonding_curve, token.bonding_curve);
+    assert_eq!(state.metadata.token_symbol, "ZGT");
+
+    // Every derived address holds an account after launch
+    for address in [
+        token.stream_history,
+        token.curve_token_account,
+        token.curve_sol_vault,
+        token.creator_fee_wallet,
+        token.holder_rewards_pool,
+        token.token_account(&creator.pubkey()),
+    ] {
+        assert!(env.lamports(&address).await > 0, "{address} not created");
+    }
+
+    let curve_data = env.data(&token.bonding_curve).await;
+    assert!(is_account::<BondingCurve>(&curve_data));
+    assert!(!is_account::<CurveReserves>(&curve_data));
+    assert!(decode::<TokenMetadata>(&curve_data).is_err());
+}
+
+#[tokio::test]
+async fn buy_and_sell_match_quotes() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let platform = env.platform_state().await;
+
+    let state = env.token_state(&token).await;
+    let clock = env.clock().await;
+    let quote = quote_buy_for_sol(
+        &state.curve,
+        &state.reserves,
+        &platform,
+        1_000_000_000,
+        clock.unix_timestamp,
+        clock.slot,
+        0,
+        false,
+    )
+    .unwrap();
+
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let vault_before = env.lamports(&token.curve_sol_vault).await;
+    let creator_fees_before = env.lamports(&token.creator_fee_wallet).await;
+    let platform_fees_before = env.lamports(&platform_fee_wallet).await;
+    let ix = instructions::buy_tokens(
+        &token,
+        &platform_fee_wallet,
+        &buyer.pubkey(),
+        quote.token_amount,
+        quote.total_cost,
+        None,
+    );
+    env.process(&[ix], &[&buyer]).await.unwrap();
+
+    assert_eq!(env.lamports(&token.curve_sol_vault).await - vault_before, quote.sol_required);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await - creator_fees_before, quote.creator_fee);
+    assert_eq!(env.lamports(&platform_fee_wallet).await - platform_fees_before, quote.platform_fee);
+    let record: BuyerRecord = env.anchor_account(&pda::buyer_record(&token.bonding_curve, &buyer.pubkey())).await;
+    assert_eq!(record.reward_balance, quote.token_amount);
+
+    env.advance_clock(1).await;
+    let state = env.token_state(&token).await;
+    let clock = env.clock().await;
+    let sell_amount = quote.token_amount / 2;
+    let quote = quote_sell(
+        &state.curve,
+        &state.reserves,
+        &platform,
+        sell_amount,
+        clock.unix_timestamp,
+        record.reward_balance,
+        false,
+    )
+    .unwrap();
+
+    let seller_before = env.lamports(&buyer.pubkey()).await;
+    let ix = instructions::sell_tokens(
+        &token,
+        &platform_fee_wallet,
+        &buyer.pubkey(),
+        sell_amount,
+        quote.net_output,
+        None,
+    );
+    env.process(&[ix], &[&buyer]).await.unwrap();
+    assert_eq!(env.lamports(&buyer.pubkey()).await - seller_before, quote.net_output);
+}
+
+#[tokio::test]
+async fn live_quotes_include_holder_rewards() {
+    let mut env = TestEnv::new().await;
+    let authority = env.payer().pubkey();
+    env.process(&[instructions::set_holder_reward_share(&authority, 5_000)], &[])
+        .await
+        .unwrap();
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+
+    let holder = env.funded_keypair(10_000_000_000).await;
+    let ix = instructions::buy_tokens(
+        &token,
+        &platform_fee_wallet,
+        &holder.pubkey(),
+        10_000_000,
+        u64::MAX,
+        None,
+    );
+    env.process(&[ix], &[&holder]).await.unwrap();
+    env.process(&[instructions::update_stream_status(&token, &authority, true)], &[])
+        .await
+        .unwrap();
+
+    let platform = env.platform_state().await;
+    let state = env.token_state(&token).await;
+    assert!(state.curve.is_live_streaming);
+    let clock = env.clock().await;
+    let quote = quote_buy(
+        &state.curve,
+        &state.reserves,
+        &platform,
+        10_000_000,
+        clock.unix_timestamp,
+        clock.slot,
+        0,
+        false,
+    )
+    .unwrap();
+    assert!(quote.holder_reward > 0);
+
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let pool_before = env.lamports(&token.holder_rewards_pool).await;
+    let ix = instructions::buy_tokens(
+        &token,
+        &platform_fee_wallet,
+        &buyer.pubkey(),
+        10_000_000,
+        quote.total_cost,
+        None,
+    );
+    env.process(&[ix], &[&buyer]).await.unwrap();
+    assert_eq!(env.lamports(&token.holder_rewards_pool).await - pool_before, quote.holder_reward);
+
+    // The live fee stops once the stream goes offline
+    env.process(&[instructions::update_stream_status(&token, &authority, false)], &[])
+        .await
+        .unwrap();
+    let state = env.token_state(&token).await;
+    let quote = quote_buy(
+        &state.curve,
+        &state.reserves,
+        &platform,
+        10_000_000,
+        clock.unix_timestamp,
+        clock.slot,
+        0,
+        false,
+    )
+    .unwrap();
+    assert_eq!(quote.holder_reward, 0);
+}
+
+#[tokio::test]
+async fn creator_withdraws_fees() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let ix = instructions::buy_tokens(
+        &token,
+        &platform_fee_wallet,
+        &buyer.pubkey(),
+        50_000_000,
+        u64::MAX,
+        None,
+    );
+    env.process(&[ix], &[&buyer]).await.unwrap();
+
+    let state = env.token_state(&token).await;
+    let pending = state.reserves.creator_fees_pending();
+    assert!(pending > 0);
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    let ix = instructions::withdraw_creator_fees(
+        &token,
+        &creator.pubkey(),
+        &state.curve.fee_recipients,
+        None,
+        None,
+    );
+    env.process(&[ix], &[&creator]).await.unwrap();
+
+    assert_eq!(env.lamports(&creator.pubkey()).await - creator_before, pending);
+    let reserves: CurveReserves = env.anchor_account(&token.curve_reserves).await;
+    assert_eq!(reserves.creator_fees_pending(), 0);
+}
@@ -0,0 +1,21 @@
+//! Replay a scenario file and print the steps
+//!
+//! cargo run -p zeroglaze-sim --example replay -- scenario.json [--json]
+
+use zeroglaze_sim::{Scenario, Simulator};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().expect("usage: replay <scenario.json> [--json]");
+    let json = args.next().as_deref() == Some("--json");
+
+    let scenario = std::fs::read_to_string(&path).expect("read scenario");
+    let scenario = Scenario::from_json(&scenario).expect("parse scenario");
+    let report = Simulator::launch(&scenario.launch).run(&scenario.events);
+
+    if json {
+        println!("{}", report.to_json().expect("encode json"));
+    } else {
+        print!("{}", report.to_csv().expect("encode csv"));
+    }
+}
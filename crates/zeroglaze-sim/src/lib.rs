@@ -0,0 +1,26 @@
+//! Deterministic off-chain replay of Zeroglaze launches.
+//!
+//! Trades run through the program's own curve math and state types
+//! (`calculate_buy_cost`, `CurveReserves::record_buy`, the holder reward
+//! accumulator, ...), so a simulated launch follows the same rounding and
+//! fee rules as the deployed program without needing a validator.
+//!
+//! ```no_run
+//! use zeroglaze_sim::{Event, LaunchConfig, Simulator};
+//!
+//! let mut sim = Simulator::launch(&LaunchConfig::default());
+//! let report = sim.run(&[
+//!     Event::StreamOn { at: 0, tier: 0 },
+//!     Event::BuyLamports { at: 5, trader: "alice".into(), lamports: 1_000_000_000 },
+//!     Event::Sell { at: 60, trader: "alice".into(), token_amount: 10_000_000 },
+//! ]);
+//! println!("{}", report.to_csv().unwrap());
+//! ```
+
+pub mod report;
+pub mod scenario;
+pub mod simulator;
+
+pub use report::{Report, Step, Summary};
+pub use scenario::{Event, LaunchConfig, Scenario};
+pub use simulator::{Simulator, Snapshot};
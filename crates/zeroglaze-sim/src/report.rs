@@ -0,0 +1,90 @@
+use serde::Serialize;
+
+/// One replayed event and the curve state after it
+///
+/// For buys `total` is what the buyer paid; for sells it is what the seller
+/// received. `creator_fee` is the part credited to the creator fee wallet,
+/// net of `holder_reward` and including any `sniper_fee`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Step {
+    pub step: usize,
+    pub at: i64,
+    pub timestamp: i64,
+    pub event: String,
+    pub trader: Option<String>,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub total: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub holder_reward: u64,
+    pub sniper_fee: u64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub tokens_sold: u64,
+    pub price: f64, // lamports per token base unit
+    pub creator_fee_bps: u16,
+    pub live: bool,
+    pub graduated: bool,
+    pub error: Option<String>,
+}
+
+/// Totals over the successful steps of a run
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    pub trades: usize,
+    pub failed: usize,
+    pub volume: u64,
+    pub platform_fees: u64,
+    pub creator_fees: u64,
+    pub holder_rewards: u64,
+    pub sniper_fees: u64,
+    pub graduated_at_step: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub summary: Summary,
+    pub steps: Vec<Step>,
+}
+
+impl Report {
+    pub fn new(steps: Vec<Step>) -> Self {
+        let mut summary = Summary::default();
+        for step in &steps {
+            if step.error.is_some() {
+                summary.failed += 1;
+                continue;
+            }
+            if step.token_amount > 0 {
+                summary.trades += 1;
+                summary.volume += step.sol_amount;
+            }
+            summary.platform_fees += step.platform_fee;
+            summary.creator_fees += step.creator_fee;
+            summary.holder_rewards += step.holder_reward;
+            summary.sniper_fees += step.sniper_fee;
+            if step.graduated && summary.graduated_at_step.is_none() {
+                summary.graduated_at_step = Some(step.step);
+            }
+        }
+        Self { summary, steps }
+    }
+
+    /// One row per step
+    pub fn to_csv(&self) -> csv::Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for step in &self.steps {
+            writer.serialize(step)?;
+        }
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+        Ok(String::from_utf8(bytes).expect("csv output is utf-8"))
+    }
+
+    /// Summary and steps as a JSON document
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use zeroglaze::constants::{
+    DEFAULT_LIVE_FEE_TIERS_BPS, DEFAULT_MAX_STREAM_STALENESS_SECS, LIVE_FEE_TIER_COUNT,
+};
+
+/// Trader name that starts with the creator allocation
+pub const CREATOR_TRADER: &str = "creator";
+
+/// Something that happens to a curve. `at` is seconds since the start of
+/// the simulation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// Buy an exact number of tokens
+    Buy { at: i64, trader: String, token_amount: u64 },
+    /// Spend `lamports` on the curve (fees come on top), buying as many
+    /// tokens as that affords
+    BuyLamports { at: i64, trader: String, lamports: u64 },
+    Sell { at: i64, trader: String, token_amount: u64 },
+    /// The creator goes live on the given live fee tier
+    StreamOn {
+        at: i64,
+        #[serde(default)]
+        tier: u8,
+    },
+    StreamOff { at: i64 },
+}
+
+impl Event {
+    pub fn at(&self) -> i64 {
+        match self {
+            Event::Buy { at, .. }
+            | Event::BuyLamports { at, .. }
+            | Event::Sell { at, .. }
+            | Event::StreamOn { at, .. }
+            | Event::StreamOff { at } => *at,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::Buy { .. } => "buy",
+            Event::BuyLamports { .. } => "buy_lamports",
+            Event::Sell { .. } => "sell",
+            Event::StreamOn { .. } => "stream_on",
+            Event::StreamOff { .. } => "stream_off",
+        }
+    }
+
+    pub fn trader(&self) -> Option<&str> {
+        match self {
+            Event::Buy { trader, .. } | Event::BuyLamports { trader, .. } | Event::Sell { trader, .. } => {
+                Some(trader)
+            }
+            Event::StreamOn { .. } | Event::StreamOff { .. } => None,
+        }
+    }
+}
+
+/// Token and platform settings for a fresh launch, mirroring the
+/// `create_token` arguments and the platform-wide fee settings
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchConfig {
+    pub launch_protection_slots: u64,
+    pub launch_max_buy_lamports: u64,
+    pub sniper_fee_bps: u16,
+    pub max_holding_bps: u16,
+    pub live_fee_tiers_bps: [u16; LIVE_FEE_TIER_COUNT],
+    pub holder_reward_share_bps: u16,
+    pub max_stream_staleness_secs: i64,
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            launch_protection_slots: 0,
+            launch_max_buy_lamports: 0,
+            sniper_fee_bps: 0,
+            max_holding_bps: 0,
+            live_fee_tiers_bps: DEFAULT_LIVE_FEE_TIERS_BPS,
+            holder_reward_share_bps: 0,
+            max_stream_staleness_secs: DEFAULT_MAX_STREAM_STALENESS_SECS,
+        }
+    }
+}
+
+/// A launch and the events to replay against it, as loaded from JSON
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub launch: LaunchConfig,
+    pub events: Vec<Event>,
+}
+
+impl Scenario {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
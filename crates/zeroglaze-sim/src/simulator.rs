@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::AccountDeserialize;
+use bytemuck::Zeroable;
+use zeroglaze::constants::{
+    ACCOUNT_RESERVED_BYTES, BONDING_CURVE_VERSION, CREATOR_SUPPLY, CURVE_SUPPLY,
+    GRADUATION_THRESHOLD_SOL, LIVE_FEE_TIER_COUNT, PLATFORM_STATE_VERSION, VIRTUAL_SOL_INITIAL,
+    VIRTUAL_TOKEN_INITIAL,
+};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::state::{BondingCurve, BuyerRecord, CurveReserves, PlatformState};
+use zeroglaze::trade::{compute_buy, compute_sell, BuyQuote, SellQuote};
+use zeroglaze::utils::calculate_tokens_for_sol;
+
+use crate::report::{Report, Step};
+use crate::scenario::{Event, LaunchConfig, CREATOR_TRADER};
+
+/// Assumed slot time, used to place events inside the launch protection window
+pub const SLOT_DURATION_MS: u64 = 400;
+
+/// The accounts a trade reads and writes
+#[derive(Clone)]
+pub struct Snapshot {
+    pub curve: BondingCurve,
+    pub reserves: CurveReserves,
+    pub platform: PlatformState,
+}
+
+impl Snapshot {
+    /// State of a freshly created token, as `create_token` leaves it at
+    /// time 0 and slot 0
+    pub fn launch(config: &LaunchConfig) -> Self {
+        let curve = BondingCurve {
+            creator: Pubkey::default(),
+            token_mint: Pubkey::default(),
+            fee_authority: Pubkey::default(),
+            pending_fee_authority: Pubkey::default(),
+            freeze_creator_allocation: false,
+            is_live_streaming: false,
+            last_stream_check: 0,
+            last_attestation_nonce: 0,
+            live_fee_tier: 0,
+            launch_protection_slots: config.launch_protection_slots,
+            launch_max_buy_lamports: config.launch_max_buy_lamports,
+            sniper_fee_bps: config.sniper_fee_bps,
+            max_holding_bps: config.max_holding_bps,
+            fee_recipients: Vec::new(),
+            created_at: 0,
+            created_slot: 0,
+            bump: 0,
+            version: BONDING_CURVE_VERSION,
+            reserved: [0; ACCOUNT_RESERVED_BYTES],
+        };
+
+        let reserves = CurveReserves {
+            virtual_sol_reserves: VIRTUAL_SOL_INITIAL,
+            virtual_token_reserves: VIRTUAL_TOKEN_INITIAL,
+            real_token_reserves: CURVE_SUPPLY,
+            ..CurveReserves::zeroed()
+        };
+
+        let platform = PlatformState {
+            authority: Pubkey::default(),
+            platform_fee_wallet: Pubkey::default(),
+            total_tokens_launched: 1,
+            total_volume_sol: 0,
+            total_fees_collected: 0,
+            max_stream_staleness_secs: config.max_stream_staleness_secs,
+            stream_oracle: Pubkey::default(),
+            live_fee_tiers_bps: config.live_fee_tiers_bps,
+            holder_reward_share_bps: config.holder_reward_share_bps,
+            referral_fee_bps: 0,
+            total_referral_fees: 0,
+            bump: 0,
+            version: PLATFORM_STATE_VERSION,
+            reserved: [0; ACCOUNT_RESERVED_BYTES],
+        };
+
+        Self { curve, reserves, platform }
+    }
+
+    /// Decode raw account data (discriminator included) fetched from a cluster
+    pub fn from_accounts(curve: &[u8], reserves: &[u8], platform: &[u8]) -> Result<Self> {
+        Ok(Self {
+            curve: BondingCurve::try_deserialize(&mut &curve[..])?,
+            reserves: CurveReserves::try_deserialize(&mut &reserves[..])?,
+            platform: PlatformState::try_deserialize(&mut &platform[..])?,
+        })
+    }
+}
+
+/// Per-trader state the program keeps in token accounts and `BuyerRecord`s
+#[derive(Clone)]
+struct Trader {
+    balance: u64,
+    record: BuyerRecord,
+}
+
+impl Trader {
+    fn new(balance: u64) -> Self {
+        Self {
+            balance,
+            record: BuyerRecord {
+                bonding_curve: Pubkey::default(),
+                buyer: Pubkey::default(),
+                launch_sol_bought: 0,
+                reward_balance: 0,
+                reward_per_token_paid: 0,
+                unclaimed_rewards: 0,
+                bump: 0,
+            },
+        }
+    }
+}
+
+/// Lamport movements of a successful trade
+#[derive(Default)]
+struct Fill {
+    token_amount: u64,
+    sol_amount: u64,
+    total: u64,
+    platform_fee: u64,
+    creator_fee: u64,
+    holder_reward: u64,
+    sniper_fee: u64,
+}
+
+/// Replays events against a curve snapshot
+///
+/// Stream status is assumed to be kept fresh by heartbeats while live, so
+/// the live fee applies from `StreamOn` until `StreamOff`. A failing event is
+/// reported with the program error it would have raised and leaves the state
+/// untouched, like a failed transaction.
+#[derive(Clone)]
+pub struct Simulator {
+    state: Snapshot,
+    traders: BTreeMap<String, Trader>,
+    start_time: i64,
+    steps: Vec<Step>,
+}
+
+impl Simulator {
+    /// `start_time` is the unix timestamp event times are offset from
+    pub fn new(state: Snapshot, start_time: i64) -> Self {
+        Self { state, traders: BTreeMap::new(), start_time, steps: Vec::new() }
+    }
+
+    /// Simulate a fresh launch; the creator holds the creator allocation
+    pub fn launch(config: &LaunchConfig) -> Self {
+        let mut sim = Self::new(Snapshot::launch(config), 0);
+        sim.set_balance(CREATOR_TRADER, CREATOR_SUPPLY);
+        sim
+    }
+
+    pub fn state(&self) -> &Snapshot {
+        &self.state
+    }
+
+    /// Tokens `trader` currently holds
+    pub fn balance(&self, trader: &str) -> u64 {
+        self.traders.get(trader).map_or(0, |t| t.balance)
+    }
+
+    /// Holder rewards `trader` could claim right now
+    pub fn unclaimed_rewards(&self, trader: &str) -> u64 {
+        let Some(t) = self.traders.get(trader) else { return 0 };
+        let mut reserves = self.state.reserves;
+        let mut record = t.record.clone();
+        match reserves.settle_holder_rewards(&mut record, t.balance) {
+            Ok(()) => record.unclaimed_rewards,
+            Err(_) => t.record.unclaimed_rewards,
+        }
+    }
+
+    /// Give `trader` tokens acquired outside the curve, e.g. for a snapshot
+    /// of a live token
+    pub fn set_balance(&mut self, trader: &str, balance: u64) {
+        self.trader_mut(trader).balance = balance;
+    }
+
+    /// Apply events in order and return the steps taken so far
+    pub fn run(&mut self, events: &[Event]) -> Report {
+        for event in events {
+            self.apply(event);
+        }
+        self.report()
+    }
+
+    pub fn report(&self) -> Report {
+        Report::new(self.steps.clone())
+    }
+
+    /// Apply a single event, returning its step
+    pub fn apply(&mut self, event: &Event) -> &Step {
+        let now = self.start_time.saturating_add(event.at());
+
+        // Roll back on failure, like a failed transaction
+        let backup = (self.state.clone(), self.traders.clone());
+        let (fill, error) = match self.execute(event, now) {
+            Ok(fill) => (fill, None),
+            Err(error) => {
+                (self.state, self.traders) = backup;
+                (Fill::default(), Some(error))
+            }
+        };
+        let step = self.step(event, now, fill, error);
+        self.steps.push(step);
+        self.steps.last().unwrap()
+    }
+
+    fn execute(&mut self, event: &Event, now: i64) -> std::result::Result<Fill, String> {
+        // Heartbeats keep a live stream's status fresh
+        if self.state.curve.is_live_streaming {
+            self.state.curve.last_stream_check = now;
+        }
+
+        match event {
+            Event::Buy { trader, token_amount, .. } => {
+                self.buy(trader, *token_amount, now).map_err(error_name)
+            }
+            Event::BuyLamports { trader, lamports, .. } => {
+                let token_amount = calculate_tokens_for_sol(
+                    self.state.reserves.virtual_sol_reserves,
+                    self.state.reserves.virtual_token_reserves,
+                    *lamports,
+                )
+                .map_err(error_name)?;
+                self.buy(trader, token_amount, now).map_err(error_name)
+            }
+            Event::Sell { trader, token_amount, .. } => {
+                if self.balance(trader) < *token_amount {
+                    // Rejected by the token program before any curve logic
+                    return Err("InsufficientFunds".to_string());
+                }
+                self.sell(trader, *token_amount, now).map_err(error_name)
+            }
+            Event::StreamOn { tier, .. } => {
+                if *tier as usize >= LIVE_FEE_TIER_COUNT {
+                    return Err(error_name(ZeroglazeError::InvalidLiveFeeTier.into()));
+                }
+                self.state.curve.is_live_streaming = true;
+                self.state.curve.live_fee_tier = *tier;
+                self.state.curve.last_stream_check = now;
+                Ok(Fill::default())
+            }
+            Event::StreamOff { .. } => {
+                self.state.curve.is_live_streaming = false;
+                self.state.curve.last_stream_check = now;
+                Ok(Fill::default())
+            }
+        }
+    }
+
+    /// Slot at `now`, assuming slots of `SLOT_DURATION_MS` since creation
+    fn slot_at(&self, now: i64) -> u64 {
+        let elapsed_secs = now.saturating_sub(self.state.curve.created_at).max(0) as u64;
+        self.state.curve.created_slot + elapsed_secs * 1_000 / SLOT_DURATION_MS
+    }
+
+    fn trader_mut(&mut self, trader: &str) -> &mut Trader {
+        self.traders.entry(trader.to_string()).or_insert_with(|| Trader::new(0))
+    }
+
+    /// Mirrors `buy_tokens`
+    fn buy(&mut self, trader: &str, token_amount: u64, now: i64) -> Result<Fill> {
+        let slot = self.slot_at(now);
+        let Snapshot { curve, reserves, platform } = &mut self.state;
+        let entry = self.traders.entry(trader.to_string()).or_insert_with(|| Trader::new(0));
+
+        reserves.settle_holder_rewards(&mut entry.record, entry.balance)?;
+
+        let BuyQuote {
+            sol_required,
+            platform_fee,
+            creator_fee,
+            holder_reward,
+            sniper_fee,
+            total_cost,
+            launch_sol_bought,
+            ..
+        } = compute_buy(
+            curve,
+            reserves,
+            platform,
+            token_amount,
+            now,
+            slot,
+            entry.record.launch_sol_bought,
+            false,
+        )?;
+        entry.record.launch_sol_bought = launch_sol_bought;
+
+        if holder_reward > 0 {
+            reserves.distribute_holder_rewards(holder_reward)?;
+        }
+
+        entry.balance = entry.balance
+            .checked_add(token_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        if let Some(max_holding) = curve.max_wallet_holding(reserves) {
+            require!(entry.balance <= max_holding, ZeroglazeError::HoldingCapExceeded);
+        }
+
+        reserves.record_buy(token_amount, sol_required, total_cost, creator_fee)?;
+        reserves.add_reward_stake(&mut entry.record, token_amount)?;
+
+        platform.record_trade(total_cost, platform_fee + creator_fee + holder_reward, 0)?;
+
+        if reserves.real_sol_reserves >= GRADUATION_THRESHOLD_SOL {
+            reserves.graduate(now);
+        }
+
+        Ok(Fill {
+            token_amount,
+            sol_amount: sol_required,
+            total: total_cost,
+            platform_fee,
+            creator_fee,
+            holder_reward,
+            sniper_fee,
+        })
+    }
+
+    /// Mirrors `sell_tokens`
+    fn sell(&mut self, trader: &str, token_amount: u64, now: i64) -> Result<Fill> {
+        let Snapshot { curve, reserves, platform } = &mut self.state;
+        let entry = self.traders.entry(trader.to_string()).or_insert_with(|| Trader::new(0));
+
+        reserves.settle_holder_rewards(&mut entry.record, entry.balance)?;
+        reserves.remove_reward_stake(&mut entry.record, token_amount)?;
+
+        let SellQuote {
+            sol_to_return,
+            platform_fee,
+            creator_fee,
+            holder_reward,
+            net_output,
+            ..
+        } = compute_sell(curve, reserves, platform, token_amount, now, false)?;
+
+        entry.balance -= token_amount;
+        if holder_reward > 0 {
+            reserves.distribute_holder_rewards(holder_reward)?;
+        }
+
+        reserves.record_sell(token_amount, sol_to_return, creator_fee)?;
+
+        platform.record_trade(sol_to_return, platform_fee + creator_fee + holder_reward, 0)?;
+
+        Ok(Fill {
+            token_amount,
+            sol_amount: sol_to_return,
+            total: net_output,
+            platform_fee,
+            creator_fee,
+            holder_reward,
+            sniper_fee: 0,
+        })
+    }
+
+    fn step(&self, event: &Event, now: i64, fill: Fill, error: Option<String>) -> Step {
+        let Snapshot { curve, reserves, platform } = &self.state;
+        Step {
+            step: self.steps.len(),
+            at: event.at(),
+            timestamp: now,
+            event: event.kind().to_string(),
+            trader: event.trader().map(str::to_string),
+            token_amount: fill.token_amount,
+            sol_amount: fill.sol_amount,
+            total: fill.total,
+            platform_fee: fill.platform_fee,
+            creator_fee: fill.creator_fee,
+            holder_reward: fill.holder_reward,
+            sniper_fee: fill.sniper_fee,
+            virtual_sol_reserves: reserves.virtual_sol_reserves,
+            virtual_token_reserves: reserves.virtual_token_reserves,
+            real_sol_reserves: reserves.real_sol_reserves,
+            real_token_reserves: reserves.real_token_reserves,
+            tokens_sold: reserves.tokens_sold,
+            price: reserves.virtual_sol_reserves as f64 / reserves.virtual_token_reserves as f64,
+            creator_fee_bps: curve.creator_fee_bps_at(now, platform),
+            live: curve.is_live_at(now, platform.max_stream_staleness_secs),
+            graduated: reserves.is_graduated(),
+            error,
+        }
+    }
+}
+
+/// Name of the program error, as shown in transaction logs
+fn error_name(error: Error) -> String {
+    match error {
+        Error::AnchorError(error) => error.error_name,
+        Error::ProgramError(error) => error.program_error.to_string(),
+    }
+}
@@ -0,0 +1,169 @@
+use zeroglaze::constants::{
+    CREATOR_FEE_LIVE_BPS, CREATOR_FEE_OFFLINE_BPS, GRADUATION_THRESHOLD_SOL, VIRTUAL_SOL_INITIAL,
+    VIRTUAL_TOKEN_INITIAL,
+};
+use zeroglaze::utils::calculate_buy_cost;
+use zeroglaze_sim::{Event, LaunchConfig, Scenario, Simulator};
+
+fn buy(at: i64, trader: &str, token_amount: u64) -> Event {
+    Event::Buy { at, trader: trader.into(), token_amount }
+}
+
+fn sell(at: i64, trader: &str, token_amount: u64) -> Event {
+    Event::Sell { at, trader: trader.into(), token_amount }
+}
+
+#[test]
+fn buy_matches_program_quote() {
+    let mut sim = Simulator::launch(&LaunchConfig::default());
+    let step = sim.apply(&buy(10, "alice", 1_000_000)).clone();
+
+    let (sol_required, platform_fee, creator_fee, total_cost) = calculate_buy_cost(
+        VIRTUAL_SOL_INITIAL,
+        VIRTUAL_TOKEN_INITIAL,
+        1_000_000,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap();
+    assert_eq!(step.error, None);
+    assert_eq!(step.sol_amount, sol_required);
+    assert_eq!(step.platform_fee, platform_fee);
+    assert_eq!(step.creator_fee, creator_fee);
+    assert_eq!(step.total, total_cost);
+    assert_eq!(step.real_sol_reserves, sol_required);
+    assert_eq!(step.tokens_sold, 1_000_000);
+    assert_eq!(sim.balance("alice"), 1_000_000);
+}
+
+#[test]
+fn stream_status_switches_creator_fee() {
+    let mut sim = Simulator::launch(&LaunchConfig::default());
+    let report = sim.run(&[
+        buy(0, "alice", 1_000_000),
+        Event::StreamOn { at: 5, tier: 0 },
+        // Well past the staleness window: heartbeats keep the stream live
+        buy(3_600, "alice", 1_000_000),
+        Event::StreamOff { at: 3_700 },
+        buy(3_800, "alice", 1_000_000),
+    ]);
+
+    let fee_bps: Vec<_> = report.steps.iter().map(|step| step.creator_fee_bps).collect();
+    assert_eq!(
+        fee_bps,
+        [
+            CREATOR_FEE_OFFLINE_BPS,
+            CREATOR_FEE_LIVE_BPS,
+            CREATOR_FEE_LIVE_BPS,
+            CREATOR_FEE_OFFLINE_BPS,
+            CREATOR_FEE_OFFLINE_BPS,
+        ]
+    );
+    assert!(report.steps[2].live);
+    assert!(report.steps[2].creator_fee > report.steps[0].creator_fee);
+}
+
+#[test]
+fn live_bonus_goes_to_existing_holders() {
+    let config = LaunchConfig { holder_reward_share_bps: 5_000, ..LaunchConfig::default() };
+    let mut sim = Simulator::launch(&config);
+    let report = sim.run(&[
+        buy(0, "alice", 10_000_000),
+        Event::StreamOn { at: 1, tier: 0 },
+        buy(2, "bob", 10_000_000),
+    ]);
+
+    let bonus = report.steps[2].holder_reward;
+    assert!(bonus > 0);
+    assert_eq!(report.summary.holder_rewards, bonus);
+    // Alice was the only eligible holder; rounding may leave dust in the pool
+    assert!(bonus - sim.unclaimed_rewards("alice") <= 1);
+    assert_eq!(sim.unclaimed_rewards("bob"), 0);
+}
+
+#[test]
+fn curve_graduates_and_stops_trading() {
+    let mut sim = Simulator::launch(&LaunchConfig::default());
+    let report = sim.run(&[
+        Event::BuyLamports {
+            at: 0,
+            trader: "whale".into(),
+            lamports: GRADUATION_THRESHOLD_SOL + 1_000_000,
+        },
+        buy(1, "alice", 1_000_000),
+        sell(2, "whale", 1_000_000),
+    ]);
+
+    assert!(report.steps[0].graduated);
+    assert!(report.steps[0].real_sol_reserves >= GRADUATION_THRESHOLD_SOL);
+    assert_eq!(report.summary.graduated_at_step, Some(0));
+    assert_eq!(report.steps[1].error.as_deref(), Some("TokenGraduated"));
+    assert_eq!(report.steps[2].error.as_deref(), Some("TokenGraduated"));
+}
+
+#[test]
+fn failed_steps_leave_state_unchanged() {
+    let config = LaunchConfig {
+        launch_protection_slots: 150,
+        launch_max_buy_lamports: 100_000_000,
+        sniper_fee_bps: 1_000,
+        max_holding_bps: 100,
+        ..LaunchConfig::default()
+    };
+    let mut sim = Simulator::launch(&config);
+    let report = sim.run(&[
+        buy(0, "alice", 1_000_000),
+        sell(1, "alice", 2_000_000),
+        buy(2, "alice", 100_000_000),
+        buy(120, "alice", 20_000_000),
+        Event::StreamOn { at: 121, tier: 9 },
+    ]);
+
+    let errors: Vec<_> = report.steps.iter().map(|step| step.error.as_deref()).collect();
+    assert_eq!(
+        errors,
+        [
+            None,
+            Some("InsufficientFunds"),
+            Some("LaunchBuyLimitExceeded"),
+            Some("HoldingCapExceeded"),
+            Some("InvalidLiveFeeTier"),
+        ]
+    );
+    assert!(report.steps[0].sniper_fee > 0);
+    for step in &report.steps[1..] {
+        assert_eq!(step.tokens_sold, 1_000_000);
+        assert_eq!(step.real_sol_reserves, report.steps[0].real_sol_reserves);
+    }
+    assert_eq!(sim.balance("alice"), 1_000_000);
+    assert_eq!(report.summary.failed, 4);
+}
+
+#[test]
+fn scenario_round_trips_to_csv_and_json() {
+    let scenario = Scenario::from_json(
+        r#"{
+            "launch": { "max_holding_bps": 500 },
+            "events": [
+                { "type": "stream_on", "at": 0 },
+                { "type": "buy_lamports", "at": 5, "trader": "alice", "lamports": 1000000000 },
+                { "type": "sell", "at": 60, "trader": "alice", "token_amount": 1000000 }
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(scenario.launch.max_holding_bps, 500);
+
+    let report = Simulator::launch(&scenario.launch).run(&scenario.events);
+    assert_eq!(report.summary.trades, 2);
+
+    let csv = report.to_csv().unwrap();
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("step,at,timestamp,event,trader,token_amount,sol_amount,total,"));
+    assert!(lines[3].starts_with("2,60,60,sell,alice,1000000,"));
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["summary"]["trades"], 2);
+    assert_eq!(json["steps"][1]["event"], "buy_lamports");
+    assert_eq!(json["steps"][2]["error"], serde_json::Value::Null);
+}
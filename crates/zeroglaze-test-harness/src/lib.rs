@@ -0,0 +1,654 @@
+//! Program-test environment shared by the Zeroglaze test suites
+//!
+//! [`TestEnv`] starts a validator with the program loaded and the platform
+//! initialized, and wraps each instruction built by
+//! [`zeroglaze_client::instructions`] in a helper that signs and sends it.
+//! Addresses come from [`zeroglaze_client::pda`], so the tests exercise the
+//! same builders and derivations that clients use.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+use zeroglaze::constants::LIVE_FEE_TIER_COUNT;
+use zeroglaze::state::{
+    BondingCurve, BuyerRecord, CurveReserves, FeeRecipient, GraduatedToken, PlatformState,
+    ReferrerStats, StreamAttestation, StreamHistory, TokenMetadata,
+};
+use zeroglaze_client::accounts::TokenState;
+use zeroglaze_client::instructions::CreateTokenArgs;
+
+pub use zeroglaze_client::{instructions, pda, TokenAccounts};
+
+/// Anchor's entry ties account lifetimes together, which the builtin
+/// processor signature cannot express.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts) };
+    zeroglaze::entry(program_id, accounts, data)
+}
+
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new("zeroglaze", zeroglaze::ID, processor!(process_instruction));
+    program_test.prefer_bpf(false);
+    program_test
+}
+
+/// Program test running the compiled `zeroglaze.so`, the only mode that
+/// meters compute units. None unless `cargo build-sbf` output is available.
+pub fn bpf_program_test() -> Option<ProgramTest> {
+    let out_dir = std::env::var("SBF_OUT_DIR")
+        .or_else(|_| std::env::var("BPF_OUT_DIR"))
+        .ok()?;
+    if !std::path::Path::new(&out_dir).join("zeroglaze.so").exists() {
+        return None;
+    }
+    let mut program_test = ProgramTest::new("zeroglaze", zeroglaze::ID, None);
+    program_test.prefer_bpf(true);
+    Some(program_test)
+}
+
+// ============================================================================
+// Test environment
+// ============================================================================
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub platform_fee_wallet: Keypair,
+}
+
+impl TestEnv {
+    /// Start a validator with the platform already initialized
+    pub async fn new() -> Self {
+        Self::with_program_test(program_test()).await
+    }
+
+    pub async fn with_program_test(program_test: ProgramTest) -> Self {
+        let context = program_test.start_with_context().await;
+        let mut env = Self {
+            context,
+            platform_fee_wallet: Keypair::new(),
+        };
+
+        let authority = env.context.payer.insecure_clone();
+        // The fee wallet must be rent exempt before it can receive small fees
+        env.airdrop(&env.platform_fee_wallet.pubkey(), 1_000_000_000).await;
+        env.initialize_platform(&authority).await.unwrap();
+        env
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let tx = self.transaction(instructions, signers).await;
+        self.context.banks_client.process_transaction(tx).await
+    }
+
+    /// Process a transaction, returning the compute units it consumed
+    pub async fn process_metered(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<u64, BanksClientError> {
+        let tx = self.transaction(instructions, signers).await;
+        let outcome = self.context.banks_client.process_transaction_with_metadata(tx).await?;
+        outcome.result?;
+        Ok(outcome.metadata.map_or(0, |metadata| metadata.compute_units_consumed))
+    }
+
+    /// Transaction paid for by the test payer and signed by `signers`
+    async fn transaction(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Transaction {
+        let payer = self.payer();
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash)
+    }
+
+    pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let payer = self.payer();
+        self.process(
+            &[system_instruction::transfer(&payer.pubkey(), to, lamports)],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Create a funded wallet
+    pub async fn funded_keypair(&mut self, lamports: u64) -> Keypair {
+        let keypair = Keypair::new();
+        self.airdrop(&keypair.pubkey(), lamports).await;
+        keypair
+    }
+
+    pub async fn transfer_tokens(
+        &mut self,
+        token: &TokenAccounts,
+        from: &Keypair,
+        to: &Pubkey,
+        amount: u64,
+    ) {
+        self.try_transfer_tokens(token, from, to, amount).await.unwrap();
+    }
+
+    pub async fn try_transfer_tokens(
+        &mut self,
+        token: &TokenAccounts,
+        from: &Keypair,
+        to: &Pubkey,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let create_ata = create_ata_ix(&self.payer().pubkey(), to, &token.token_mint);
+        let transfer = anchor_spl::token::spl_token::instruction::transfer(
+            &anchor_spl::token::ID,
+            &token.token_account(&from.pubkey()),
+            &token.token_account(to),
+            &from.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.process(&[create_ata, transfer], &[from]).await
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(*address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn warp_slots(&mut self, slots: u64) {
+        let slot = self.context.banks_client.get_root_slot().await.unwrap();
+        self.context.warp_to_slot(slot + slots).unwrap();
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context.banks_client.get_sysvar().await.unwrap()
+    }
+
+    /// Move the cluster clock forward without advancing slots
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn rent_exempt_minimum(&mut self, data_len: usize) -> u64 {
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        rent.minimum_balance(data_len)
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        self.token_account(address).await.amount
+    }
+
+    pub async fn token_account(&mut self, address: &Pubkey) -> anchor_spl::token::TokenAccount {
+        self.anchor_account(address).await
+    }
+
+    /// Raw data of an account that must exist
+    pub async fn data(&mut self, address: &Pubkey) -> Vec<u8> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("account not found")
+            .data
+    }
+
+    pub async fn anchor_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        T::try_deserialize(&mut self.data(address).await.as_slice()).unwrap()
+    }
+
+    pub async fn bonding_curve(&mut self, token: &TokenAccounts) -> BondingCurve {
+        self.anchor_account(&token.bonding_curve).await
+    }
+
+    pub async fn curve_reserves(&mut self, token: &TokenAccounts) -> CurveReserves {
+        self.anchor_account(&token.curve_reserves).await
+    }
+
+    pub async fn token_metadata(&mut self, token: &TokenAccounts) -> TokenMetadata {
+        self.anchor_account(&token.token_metadata).await
+    }
+
+    /// A token's curve, reserves and metadata, decoded the way clients do
+    pub async fn token_state(&mut self, token: &TokenAccounts) -> TokenState {
+        let curve = self.data(&token.bonding_curve).await;
+        let reserves = self.data(&token.curve_reserves).await;
+        let metadata = self.data(&token.token_metadata).await;
+        TokenState::decode(&curve, &reserves, &metadata).unwrap()
+    }
+
+    pub async fn buyer_record(&mut self, token: &TokenAccounts, buyer: &Pubkey) -> BuyerRecord {
+        self.anchor_account(&pda::buyer_record(&token.bonding_curve, buyer))
+            .await
+    }
+
+    pub async fn stream_history(&mut self, token: &TokenAccounts) -> StreamHistory {
+        self.anchor_account(&token.stream_history).await
+    }
+
+    pub async fn referrer_stats(&mut self, referrer: &Pubkey) -> ReferrerStats {
+        self.anchor_account(&pda::referrer_stats(referrer)).await
+    }
+
+    pub async fn graduated_token(&mut self, token: &TokenAccounts) -> GraduatedToken {
+        self.anchor_account(&pda::graduated_token(&token.token_mint)).await
+    }
+
+    pub async fn platform_state(&mut self) -> PlatformState {
+        self.anchor_account(&pda::platform_state()).await
+    }
+
+    /// Overwrite a bonding curve's on-chain state, bypassing the program
+    pub async fn set_bonding_curve(&mut self, token: &TokenAccounts, curve: &BondingCurve) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(token.bonding_curve)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut data = Vec::with_capacity(account.data.len());
+        curve.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.context.set_account(&token.bonding_curve, &account.into());
+    }
+
+    /// Overwrite a curve's reserves, bypassing the program
+    pub async fn set_curve_reserves(&mut self, token: &TokenAccounts, reserves: &CurveReserves) {
+        let mut account = self
+            .context
+            .banks_client
+            .get_account(token.curve_reserves)
+            .await
+            .unwrap()
+            .unwrap();
+        account.data[8..8 + CurveReserves::LEN].copy_from_slice(bytemuck::bytes_of(reserves));
+        self.context.set_account(&token.curve_reserves, &account.into());
+    }
+
+    // ========================================================================
+    // Instructions
+    // ========================================================================
+
+    pub async fn initialize_platform(
+        &mut self,
+        authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::initialize_platform(&authority.pubkey(), &self.platform_fee_wallet.pubkey());
+        self.process(&[ix], &[authority]).await
+    }
+
+    /// Arguments for an unprotected launch with test metadata
+    pub fn create_token_args(freeze_creator_allocation: bool) -> CreateTokenArgs {
+        CreateTokenArgs {
+            token_name: "Zeroglaze Test".to_string(),
+            token_symbol: "ZGT".to_string(),
+            token_uri: "https://zeroglaze.fun/zgt.json".to_string(),
+            creator_twitter: "zeroglaze".to_string(),
+            creator_twitch: "zeroglaze".to_string(),
+            freeze_creator_allocation,
+            launch_protection_slots: 0,
+            launch_max_buy_lamports: 0,
+            sniper_fee_bps: 0,
+            max_holding_bps: 0,
+            initial_buy_lamports: None,
+            fee_recipients: vec![],
+        }
+    }
+
+    pub async fn create_token_with(
+        &mut self,
+        creator: &Keypair,
+        args: CreateTokenArgs,
+    ) -> std::result::Result<TokenAccounts, BanksClientError> {
+        let ix = instructions::create_token(&creator.pubkey(), &self.platform_fee_wallet.pubkey(), args);
+        self.process(&[ix], &[creator]).await?;
+        Ok(TokenAccounts::new(&creator.pubkey()))
+    }
+
+    pub async fn create_token(
+        &mut self,
+        creator: &Keypair,
+        freeze_creator_allocation: bool,
+    ) -> std::result::Result<TokenAccounts, BanksClientError> {
+        self.create_token_with(creator, Self::create_token_args(freeze_creator_allocation))
+            .await
+    }
+
+    pub async fn buy(
+        &mut self,
+        token: &TokenAccounts,
+        buyer: &Keypair,
+        token_amount: u64,
+        max_sol_cost: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::buy_tokens(
+            token,
+            &self.platform_fee_wallet.pubkey(),
+            &buyer.pubkey(),
+            token_amount,
+            max_sol_cost,
+            None,
+        );
+        self.process(&[ix], &[buyer]).await
+    }
+
+    pub async fn buy_referred(
+        &mut self,
+        token: &TokenAccounts,
+        buyer: &Keypair,
+        token_amount: u64,
+        referrer: &Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::buy_tokens(
+            token,
+            &self.platform_fee_wallet.pubkey(),
+            &buyer.pubkey(),
+            token_amount,
+            u64::MAX,
+            Some(referrer),
+        );
+        self.process(&[ix], &[buyer]).await
+    }
+
+    pub async fn sell(
+        &mut self,
+        token: &TokenAccounts,
+        seller: &Keypair,
+        token_amount: u64,
+        min_sol_output: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::sell_tokens(
+            token,
+            &self.platform_fee_wallet.pubkey(),
+            &seller.pubkey(),
+            token_amount,
+            min_sol_output,
+            None,
+        );
+        self.process(&[ix], &[seller]).await
+    }
+
+    pub async fn sell_referred(
+        &mut self,
+        token: &TokenAccounts,
+        seller: &Keypair,
+        token_amount: u64,
+        referrer: &Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::sell_tokens(
+            token,
+            &self.platform_fee_wallet.pubkey(),
+            &seller.pubkey(),
+            token_amount,
+            0,
+            Some(referrer),
+        );
+        self.process(&[ix], &[seller]).await
+    }
+
+    pub async fn register_referrer(&mut self, referrer: &Keypair) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::register_referrer(&referrer.pubkey());
+        self.process(&[ix], &[referrer]).await
+    }
+
+    pub async fn update_stream_status(
+        &mut self,
+        token: &TokenAccounts,
+        authority: &Keypair,
+        is_live: bool,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::update_stream_status(token, &authority.pubkey(), is_live);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn update_stream_tier(
+        &mut self,
+        token: &TokenAccounts,
+        authority: &Keypair,
+        live_fee_tier: u8,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::update_stream_tier(token, &authority.pubkey(), live_fee_tier);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn batch_update_stream_status(
+        &mut self,
+        tokens: &[&TokenAccounts],
+        authority: &Keypair,
+        statuses: Vec<bool>,
+    ) -> std::result::Result<(), BanksClientError> {
+        let tokens: Vec<TokenAccounts> = tokens.iter().map(|token| **token).collect();
+        let ix = instructions::batch_update_stream_status(&tokens, &authority.pubkey(), statuses);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn stream_heartbeat(
+        &mut self,
+        token: &TokenAccounts,
+        authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::stream_heartbeat(token, &authority.pubkey());
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn set_max_stream_staleness(
+        &mut self,
+        authority: &Keypair,
+        max_stream_staleness_secs: i64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::set_max_stream_staleness(&authority.pubkey(), max_stream_staleness_secs);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn set_live_fee_tiers(
+        &mut self,
+        authority: &Keypair,
+        live_fee_tiers_bps: [u16; LIVE_FEE_TIER_COUNT],
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::set_live_fee_tiers(&authority.pubkey(), live_fee_tiers_bps);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn set_holder_reward_share(
+        &mut self,
+        authority: &Keypair,
+        holder_reward_share_bps: u16,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::set_holder_reward_share(&authority.pubkey(), holder_reward_share_bps);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn set_referral_fee(
+        &mut self,
+        authority: &Keypair,
+        referral_fee_bps: u16,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::set_referral_fee(&authority.pubkey(), referral_fee_bps);
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn set_stream_oracle(
+        &mut self,
+        authority: &Keypair,
+        stream_oracle: Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::set_stream_oracle(&authority.pubkey(), &stream_oracle);
+        self.process(&[ix], &[authority]).await
+    }
+
+    /// Relay an attestation signed by `oracle`, paid for by the test payer
+    pub async fn relay_stream_attestation(
+        &mut self,
+        token: &TokenAccounts,
+        oracle: &Keypair,
+        attestation: StreamAttestation,
+    ) -> std::result::Result<(), BanksClientError> {
+        let message = instructions::attestation_message(&attestation);
+        let ixs = [
+            ed25519_verify_ix(oracle, &message),
+            instructions::relay_stream_attestation(token, attestation),
+        ];
+        self.process(&ixs, &[]).await
+    }
+
+    pub async fn claim_holder_rewards(
+        &mut self,
+        token: &TokenAccounts,
+        holder: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::claim_holder_rewards(token, &holder.pubkey());
+        self.process(&[ix], &[holder]).await
+    }
+
+    pub async fn update_fee_recipients(
+        &mut self,
+        token: &TokenAccounts,
+        creator: &Keypair,
+        fee_recipients: Vec<FeeRecipient>,
+        cosigners: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let cosigner_keys: Vec<Pubkey> = cosigners.iter().map(|cosigner| cosigner.pubkey()).collect();
+        let ix = instructions::update_fee_recipients(token, &creator.pubkey(), fee_recipients, &cosigner_keys);
+        let mut signers = vec![creator];
+        signers.extend_from_slice(cosigners);
+        self.process(&[ix], &signers).await
+    }
+
+    pub async fn transfer_creator(
+        &mut self,
+        token: &TokenAccounts,
+        fee_authority: &Keypair,
+        new_authority: &Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::transfer_creator(token, &fee_authority.pubkey(), new_authority);
+        self.process(&[ix], &[fee_authority]).await
+    }
+
+    pub async fn accept_creator(
+        &mut self,
+        token: &TokenAccounts,
+        new_authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::accept_creator(token, &new_authority.pubkey());
+        self.process(&[ix], &[new_authority]).await
+    }
+
+    pub async fn migrate_platform(
+        &mut self,
+        authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::migrate_platform(&authority.pubkey());
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn migrate_curve(
+        &mut self,
+        token: &TokenAccounts,
+        payer: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::migrate_curve(token, &payer.pubkey());
+        self.process(&[ix], &[payer]).await
+    }
+
+    /// Withdraw a graduated curve's liquidity to its `dex_liquidity` PDA
+    pub async fn withdraw_graduated_liquidity(
+        &mut self,
+        token: &TokenAccounts,
+        authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::withdraw_graduated_liquidity(
+            token,
+            &authority.pubkey(),
+            &self.platform_fee_wallet.pubkey(),
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn close_curve(
+        &mut self,
+        token: &TokenAccounts,
+        fee_authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = instructions::close_curve(token, &fee_authority.pubkey(), &self.platform_fee_wallet.pubkey());
+        self.process(&[ix], &[fee_authority]).await
+    }
+
+    /// Withdraw all pending creator fees, passing the curve's current fee recipients
+    pub async fn withdraw_creator_fees(
+        &mut self,
+        token: &TokenAccounts,
+        creator: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        self.withdraw_creator_fees_to(token, creator, None, None).await
+    }
+
+    pub async fn withdraw_creator_fees_to(
+        &mut self,
+        token: &TokenAccounts,
+        creator: &Keypair,
+        amount: Option<u64>,
+        destination: Option<&Pubkey>,
+    ) -> std::result::Result<(), BanksClientError> {
+        let recipients = self.bonding_curve(token).await.fee_recipients;
+        let ix = instructions::withdraw_creator_fees(token, &creator.pubkey(), &recipients, amount, destination);
+        self.process(&[ix], &[creator]).await
+    }
+}
+
+/// CreateIdempotent on the associated token program
+pub fn create_ata_ix(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: anchor_spl::associated_token::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(get_associated_token_address(owner, mint), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+        ],
+        data: vec![1],
+    }
+}
+
+/// Ed25519 program instruction carrying `signer`'s signature over `message`
+pub fn ed25519_verify_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    let signature: [u8; 64] = signer.sign_message(message).into();
+    instructions::ed25519_verify(&signer.pubkey(), &signature, message)
+}
+
+/// Assert that a transaction failed with the given program error
+pub fn assert_program_error(
+    result: std::result::Result<(), BanksClientError>,
+    expected: zeroglaze::errors::ZeroglazeError,
+) {
+    let expected_code = u32::from(expected);
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, expected_code, "unexpected program error"),
+        other => panic!("expected program error {}, got {:?}", expected_code, other),
+    }
+}
@@ -0,0 +1,66 @@
+//! Random buy/sell sequences against the curve math.
+//!
+//! cargo +nightly fuzz run curve_trades
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use zeroglaze::constants::{CURVE_SUPPLY, MAX_CREATOR_FEE_BPS, VIRTUAL_SOL_INITIAL, VIRTUAL_TOKEN_INITIAL};
+use zeroglaze::utils::{calculate_buy_cost, calculate_sell_output};
+
+#[derive(Arbitrary, Debug)]
+enum Trade {
+    Buy(u64),
+    Sell(u64),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    fee_bps: u16,
+    trades: Vec<Trade>,
+}
+
+fuzz_target!(|input: Input| {
+    let fee_bps = input.fee_bps % (MAX_CREATOR_FEE_BPS + 1);
+    let mut virtual_sol = VIRTUAL_SOL_INITIAL;
+    let mut virtual_token = VIRTUAL_TOKEN_INITIAL;
+    let mut tokens_sold = 0;
+
+    for trade in input.trades {
+        let k = virtual_sol as u128 * virtual_token as u128;
+        match trade {
+            Trade::Buy(amount) => {
+                let amount = amount % (CURVE_SUPPLY - tokens_sold + 1);
+                if amount == 0 {
+                    continue;
+                }
+                let (sol_required, _, _, total_cost) =
+                    calculate_buy_cost(virtual_sol, virtual_token, amount, fee_bps).expect("valid buy failed");
+                assert!(total_cost >= sol_required);
+                virtual_sol += sol_required;
+                virtual_token -= amount;
+                tokens_sold += amount;
+
+                // Selling straight back never returns more than was paid
+                let (_, _, _, net_output) =
+                    calculate_sell_output(virtual_sol, virtual_token, amount, fee_bps).expect("valid sell failed");
+                assert!(net_output <= total_cost);
+            }
+            Trade::Sell(amount) => {
+                let amount = amount % (tokens_sold + 1);
+                if amount == 0 {
+                    continue;
+                }
+                let (sol_to_return, _, _, net_output) =
+                    calculate_sell_output(virtual_sol, virtual_token, amount, fee_bps).expect("valid sell failed");
+                assert!(net_output <= sol_to_return);
+                virtual_sol -= sol_to_return;
+                virtual_token += amount;
+                tokens_sold -= amount;
+            }
+        }
+        assert!(virtual_sol as u128 * virtual_token as u128 >= k, "k shrank");
+        assert!(virtual_sol >= VIRTUAL_SOL_INITIAL);
+    }
+});
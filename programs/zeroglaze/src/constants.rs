@@ -2,6 +2,7 @@
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000; // 1 billion
 pub const CURVE_SUPPLY: u64 = 800_000_000;   // 800 million tradeable
 pub const CREATOR_SUPPLY: u64 = 200_000_000; // 200 million to creator
+pub const CREATOR_ALLOCATION_BPS: u64 = CREATOR_SUPPLY * BPS_DENOMINATOR / TOTAL_SUPPLY; // 20%
 
 /// Virtual reserves for bonding curve
 pub const VIRTUAL_SOL_INITIAL: u64 = 30_000_000_000; // 30 SOL in lamports
@@ -12,9 +13,45 @@ pub const PLATFORM_FEE_BPS: u16 = 100;         // 1%
 pub const CREATOR_FEE_LIVE_BPS: u16 = 200;     // 2% when live
 pub const CREATOR_FEE_OFFLINE_BPS: u16 = 20;   // 0.2% when offline
 
+/// Live creator fee tiers, indexed by the keeper-reported stream tier
+pub const LIVE_FEE_TIER_COUNT: usize = 4;
+pub const DEFAULT_LIVE_FEE_TIERS_BPS: [u16; LIVE_FEE_TIER_COUNT] = [CREATOR_FEE_LIVE_BPS; LIVE_FEE_TIER_COUNT];
+pub const MAX_CREATOR_FEE_BPS: u16 = 1_000;    // 10%
+
+/// Maximum number of creator fee recipients per token
+pub const MAX_FEE_RECIPIENTS: usize = 4;
+
+/// Fixed-point scale for the holder reward-per-token accumulator
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Stream status is treated as offline once the keeper stops reporting
+pub const DEFAULT_MAX_STREAM_STALENESS_SECS: i64 = 300; // 5 minutes
+
+/// Number of recent stream sessions kept per token
+pub const STREAM_HISTORY_LEN: usize = 8;
+
+/// Account layout versions, bumped whenever a migrate_* instruction is needed
+pub const PLATFORM_STATE_VERSION: u8 = 1;
+pub const BONDING_CURVE_VERSION: u8 = 2;
+
+/// Zeroed padding kept at the end of versioned accounts for future fields
+pub const ACCOUNT_RESERVED_BYTES: usize = 64;
+
 /// Graduation thresholds
 pub const GRADUATION_THRESHOLD_SOL: u64 = 85_000_000_000; // 85 SOL in lamports
 pub const GRADUATION_FEE_SOL: u64 = 6_000_000_000;        // 6 SOL in lamports
 
+/// Rounding dust allowed to remain owed to holders when a curve is closed
+/// inside the claim window; it goes to the platform fee wallet
+pub const MAX_CLOSE_REWARD_DUST_LAMPORTS: u64 = 10_000;
+
+/// Time holders have after graduation to claim rewards before a curve can be
+/// closed and what is left in its rewards pool swept to the platform
+pub const HOLDER_REWARD_CLAIM_WINDOW_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
+
 /// Precision for calculations
 pub const BPS_DENOMINATOR: u64 = 10_000; // 100% = 10,000 basis points
+
+/// Launch protection limits
+pub const MAX_LAUNCH_PROTECTION_SLOTS: u64 = 750; // ~5 minutes at 400ms slots
+pub const MAX_SNIPER_FEE_BPS: u16 = 5_000;        // 50% at launch, decaying to 0
@@ -37,4 +37,88 @@ pub enum ZeroglazeError {
 
     #[msg("Invalid fee calculation")]
     InvalidFee,
+
+    #[msg("Curve reserves do not match on-chain balances")]
+    InvariantViolation,
+
+    #[msg("Invalid launch protection settings")]
+    InvalidLaunchProtection,
+
+    #[msg("Per-wallet buy limit exceeded during launch protection")]
+    LaunchBuyLimitExceeded,
+
+    #[msg("Invalid holding cap (2,000 to 10,000 basis points, or 0 for none)")]
+    InvalidHoldingCap,
+
+    #[msg("Purchase would exceed the per-wallet holding cap")]
+    HoldingCapExceeded,
+
+    #[msg("Initial buy would graduate the token at launch")]
+    InitialBuyTooLarge,
+
+    #[msg("Stream staleness limit must be positive")]
+    InvalidStreamStaleness,
+
+    #[msg("Stream status count does not match bonding curve accounts")]
+    StreamBatchLengthMismatch,
+
+    #[msg("Account is not a valid bonding curve")]
+    InvalidBondingCurve,
+
+    #[msg("No stream oracle is registered")]
+    OracleNotConfigured,
+
+    #[msg("Missing or invalid ed25519 stream attestation")]
+    InvalidAttestation,
+
+    #[msg("Stream attestation is too old")]
+    StaleAttestation,
+
+    #[msg("Stream attestation nonce already used")]
+    AttestationReplayed,
+
+    #[msg("Live fee tier out of range")]
+    InvalidLiveFeeTier,
+
+    #[msg("Live fee tiers must be non-decreasing, between the offline fee and the max creator fee")]
+    InvalidLiveFeeTiers,
+
+    #[msg("Invalid holder reward share (max 10,000 basis points)")]
+    InvalidHolderRewardShare,
+
+    #[msg("No holder rewards to claim")]
+    NoRewardsToClaim,
+
+    #[msg("Invalid referral fee (max 10,000 basis points)")]
+    InvalidReferralFee,
+
+    #[msg("Referrer and referrer stats must be passed together and may not be the trader")]
+    InvalidReferrer,
+
+    #[msg("Fee recipients must be unique, non-zero weights summing to 10,000 basis points")]
+    InvalidFeeRecipients,
+
+    #[msg("A fee recipient whose share is reduced must sign")]
+    MissingRecipientConsent,
+
+    #[msg("Withdrawal exceeds available creator fees")]
+    InsufficientCreatorFees,
+
+    #[msg("Token has not graduated")]
+    TokenNotGraduated,
+
+    #[msg("Curve still holds liquidity, fees or unclaimed rewards")]
+    CurveNotDrained,
+
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Destination would receive nothing: the fee authority has no fee share")]
+    UnusedDestination,
+
+    #[msg("Graduated liquidity has already been withdrawn")]
+    LiquidityAlreadyWithdrawn,
+
+    #[msg("Account layout version is not one this program can migrate")]
+    UnsupportedAccountVersion,
 }
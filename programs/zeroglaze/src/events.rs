@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+/// Creator buy executed atomically inside `create_token`
+#[event]
+pub struct CreatorInitialBuy {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub timestamp: i64,
+}
+
+/// A transfer out of the creator fee wallet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeePayout {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+/// Creator fees paid out of a token's creator fee wallet
+#[event]
+pub struct CreatorFeesWithdrawn {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub payouts: Vec<FeePayout>, // every wallet actually paid
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
+
+/// Fee authority handed over through transfer_creator / accept_creator
+#[event]
+pub struct CreatorTransferred {
+    pub token_mint: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Graduated curve's liquidity moved out for the DEX pool
+#[event]
+pub struct GraduatedLiquidityWithdrawn {
+    pub token_mint: Pubkey,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub graduation_fee: u64,
+    pub timestamp: i64,
+}
+
+/// Graduated curve closed and its rent returned to the creator
+#[event]
+pub struct CurveClosed {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub reclaimed_lamports: u64,
+    pub forfeited_rewards: u64, // rewards pool remainder sent to the platform
+    pub timestamp: i64,
+}
+
+/// Holder rewards paid out of a token's rewards pool
+#[event]
+pub struct HolderRewardsClaimed {
+    pub token_mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
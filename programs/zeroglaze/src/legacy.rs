@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::constants::{
+    ACCOUNT_RESERVED_BYTES, BONDING_CURVE_VERSION, DEFAULT_LIVE_FEE_TIERS_BPS,
+    DEFAULT_MAX_STREAM_STALENESS_SECS, PLATFORM_STATE_VERSION,
+};
+use crate::state::{BondingCurve, CurveReserves, PlatformState, StreamHistory, TokenMetadata};
+
+// Account layouts deployed before versioning. They share the discriminator of
+// the current account types and are only read by the migrate_* instructions.
+
+/// PlatformState as originally deployed
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone)]
+pub struct PlatformStateV0 {
+    pub authority: Pubkey,
+    pub platform_fee_wallet: Pubkey,
+    pub total_tokens_launched: u64,
+    pub total_volume_sol: u64,
+    pub total_fees_collected: u64,
+    pub bump: u8,
+}
+
+impl From<PlatformStateV0> for PlatformState {
+    /// Fields added since v0 take the values initialize_platform gives them
+    fn from(v0: PlatformStateV0) -> Self {
+        Self {
+            authority: v0.authority,
+            platform_fee_wallet: v0.platform_fee_wallet,
+            total_tokens_launched: v0.total_tokens_launched,
+            total_volume_sol: v0.total_volume_sol,
+            total_fees_collected: v0.total_fees_collected,
+            max_stream_staleness_secs: DEFAULT_MAX_STREAM_STALENESS_SECS,
+            stream_oracle: Pubkey::default(),
+            live_fee_tiers_bps: DEFAULT_LIVE_FEE_TIERS_BPS,
+            holder_reward_share_bps: 0,
+            referral_fee_bps: 0,
+            total_referral_fees: 0,
+            bump: v0.bump,
+            version: PLATFORM_STATE_VERSION,
+            reserved: [0; ACCOUNT_RESERVED_BYTES],
+        }
+    }
+}
+
+/// BondingCurve as originally deployed, with reserves and metadata inline
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone)]
+pub struct BondingCurveV0 {
+    pub creator: Pubkey,
+    pub token_mint: Pubkey,
+    #[max_len(32)]
+    pub token_name: String,
+    #[max_len(10)]
+    pub token_symbol: String,
+    #[max_len(200)]
+    pub token_uri: String,
+    #[max_len(32)]
+    pub creator_twitter: String,
+    #[max_len(32)]
+    pub creator_twitch: String,
+    pub freeze_creator_allocation: bool,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub tokens_sold: u64,
+    pub total_volume: u64,
+    pub creator_fees_collected: u64,
+    pub is_live_streaming: bool,
+    pub last_stream_check: i64,
+    pub graduated: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl BondingCurveV0 {
+    /// Write this curve's fields into the current layout and its companion
+    /// accounts
+    ///
+    /// Fields added since v0 start as create_token leaves them with every
+    /// optional feature off: the creator holds the fee authority, there is no
+    /// launch protection, holding cap, fee split or holder reward stake.
+    /// `creator_fees_withdrawn` is not known here and is left at zero for the
+    /// caller to reconcile against the fee wallet.
+    pub fn split_into(
+        self,
+        reserves: &mut CurveReserves,
+        metadata: &mut TokenMetadata,
+        stream_history: &mut StreamHistory,
+    ) -> BondingCurve {
+        reserves.virtual_sol_reserves = self.virtual_sol_reserves;
+        reserves.virtual_token_reserves = self.virtual_token_reserves;
+        reserves.real_sol_reserves = self.real_sol_reserves;
+        reserves.real_token_reserves = self.real_token_reserves;
+        reserves.tokens_sold = self.tokens_sold;
+        reserves.total_volume = self.total_volume;
+        reserves.creator_fees_collected = self.creator_fees_collected;
+        // graduated_at stays 0: V0 curves never paid holder rewards, so there
+        // is nothing to hold the close open for
+        reserves.graduated = self.graduated as u8;
+
+        metadata.token_name = self.token_name;
+        metadata.token_symbol = self.token_symbol;
+        metadata.token_uri = self.token_uri;
+        metadata.creator_twitter = self.creator_twitter;
+        metadata.creator_twitch = self.creator_twitch;
+
+        // A stream that is live at migration is tracked from its last check
+        if self.is_live_streaming {
+            stream_history.current_session_start = self.last_stream_check;
+            stream_history.session_count = 1;
+        }
+
+        BondingCurve {
+            creator: self.creator,
+            token_mint: self.token_mint,
+            fee_authority: self.creator,
+            pending_fee_authority: Pubkey::default(),
+            freeze_creator_allocation: self.freeze_creator_allocation,
+            is_live_streaming: self.is_live_streaming,
+            last_stream_check: self.last_stream_check,
+            last_attestation_nonce: 0,
+            live_fee_tier: 0,
+            launch_protection_slots: 0,
+            launch_max_buy_lamports: 0,
+            sniper_fee_bps: 0,
+            max_holding_bps: 0,
+            fee_recipients: Vec::new(),
+            created_at: self.created_at,
+            created_slot: 0,
+            bump: self.bump,
+            version: BONDING_CURVE_VERSION,
+            reserved: [0; ACCOUNT_RESERVED_BYTES],
+        }
+    }
+}
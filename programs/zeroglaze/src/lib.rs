@@ -1,18 +1,25 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo, Transfer, FreezeAccount};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
 
 declare_id!("ZERO11111111111111111111111111111111111111111");
 
 pub mod state;
 pub mod errors;
 pub mod constants;
+pub mod events;
 pub mod utils;
+pub mod trade;
+pub mod legacy;
 
 use state::*;
 use errors::*;
 use constants::*;
+use events::*;
 use utils::*;
+use trade::*;
 
 #[program]
 pub mod zeroglaze {
@@ -29,13 +36,22 @@ pub mod zeroglaze {
         platform_state.total_tokens_launched = 0;
         platform_state.total_volume_sol = 0;
         platform_state.total_fees_collected = 0;
+        platform_state.max_stream_staleness_secs = DEFAULT_MAX_STREAM_STALENESS_SECS;
+        platform_state.stream_oracle = Pubkey::default();
+        platform_state.live_fee_tiers_bps = DEFAULT_LIVE_FEE_TIERS_BPS;
+        platform_state.holder_reward_share_bps = 0;
+        platform_state.referral_fee_bps = 0;
+        platform_state.total_referral_fees = 0;
         platform_state.bump = ctx.bumps.platform_state;
+        platform_state.version = PLATFORM_STATE_VERSION;
+        platform_state.reserved = [0; ACCOUNT_RESERVED_BYTES];
 
         msg!("Platform initialized with authority: {}", ctx.accounts.authority.key());
         Ok(())
     }
 
     /// Create a new token with bonding curve
+    #[allow(clippy::too_many_arguments)]
     pub fn create_token(
         ctx: Context<CreateToken>,
         token_name: String,
@@ -44,10 +60,29 @@ pub mod zeroglaze {
         creator_twitter: String,
         creator_twitch: String,
         freeze_creator_allocation: bool,
+        launch_protection_slots: u64,
+        launch_max_buy_lamports: u64,
+        sniper_fee_bps: u16,
+        max_holding_bps: u16,
+        initial_buy_lamports: Option<u64>,
+        fee_recipients: Vec<FeeRecipient>,
     ) -> Result<()> {
         require!(token_name.len() <= 32, ZeroglazeError::NameTooLong);
         require!(token_symbol.len() <= 10, ZeroglazeError::SymbolTooLong);
         require!(token_uri.len() <= 200, ZeroglazeError::UriTooLong);
+        require!(
+            launch_protection_slots <= MAX_LAUNCH_PROTECTION_SLOTS
+                && sniper_fee_bps <= MAX_SNIPER_FEE_BPS,
+            ZeroglazeError::InvalidLaunchProtection
+        );
+        // The creator's allocation counts towards the cap, so a cap below it
+        // would lock the creator out of buying
+        require!(
+            max_holding_bps == 0
+                || (CREATOR_ALLOCATION_BPS..=BPS_DENOMINATOR).contains(&(max_holding_bps as u64)),
+            ZeroglazeError::InvalidHoldingCap
+        );
+        validate_fee_recipients(&fee_recipients)?;
 
         let bonding_curve = &mut ctx.accounts.bonding_curve;
         let clock = Clock::get()?;
@@ -55,32 +90,82 @@ pub mod zeroglaze {
         // Initialize bonding curve state
         bonding_curve.creator = ctx.accounts.creator.key();
         bonding_curve.token_mint = ctx.accounts.token_mint.key();
-        bonding_curve.token_name = token_name;
-        bonding_curve.token_symbol = token_symbol;
-        bonding_curve.token_uri = token_uri;
-        bonding_curve.creator_twitter = creator_twitter;
-        bonding_curve.creator_twitch = creator_twitch;
+        bonding_curve.fee_authority = ctx.accounts.creator.key();
+        bonding_curve.pending_fee_authority = Pubkey::default();
         bonding_curve.freeze_creator_allocation = freeze_creator_allocation;
 
-        bonding_curve.virtual_sol_reserves = VIRTUAL_SOL_INITIAL;
-        bonding_curve.virtual_token_reserves = VIRTUAL_TOKEN_INITIAL;
-        bonding_curve.real_sol_reserves = 0;
-        bonding_curve.real_token_reserves = CURVE_SUPPLY;
-
-        bonding_curve.tokens_sold = 0;
         bonding_curve.is_live_streaming = false;
         bonding_curve.last_stream_check = clock.unix_timestamp;
-        bonding_curve.graduated = false;
-        bonding_curve.total_volume = 0;
-        bonding_curve.creator_fees_collected = 0;
+        bonding_curve.last_attestation_nonce = 0;
+        bonding_curve.live_fee_tier = 0;
+
+        bonding_curve.launch_protection_slots = launch_protection_slots;
+        bonding_curve.launch_max_buy_lamports = launch_max_buy_lamports;
+        bonding_curve.sniper_fee_bps = sniper_fee_bps;
+        bonding_curve.max_holding_bps = max_holding_bps;
+
+        bonding_curve.fee_recipients = fee_recipients;
 
         bonding_curve.created_at = clock.unix_timestamp;
+        bonding_curve.created_slot = clock.slot;
         bonding_curve.bump = ctx.bumps.bonding_curve;
+        bonding_curve.version = BONDING_CURVE_VERSION;
+        bonding_curve.reserved = [0; ACCOUNT_RESERVED_BYTES];
+
+        // Trading state starts zeroed; only non-zero fields are set
+        let mut reserves = ctx.accounts.curve_reserves.load_init()?;
+        reserves.virtual_sol_reserves = VIRTUAL_SOL_INITIAL;
+        reserves.virtual_token_reserves = VIRTUAL_TOKEN_INITIAL;
+        reserves.real_token_reserves = CURVE_SUPPLY;
+        reserves.bonding_curve = bonding_curve.key();
+        reserves.bump = ctx.bumps.curve_reserves;
+
+        let token_metadata = &mut ctx.accounts.token_metadata;
+        token_metadata.bonding_curve = bonding_curve.key();
+        token_metadata.token_name = token_name;
+        token_metadata.token_symbol = token_symbol;
+        token_metadata.token_uri = token_uri;
+        token_metadata.creator_twitter = creator_twitter;
+        token_metadata.creator_twitch = creator_twitch;
+        token_metadata.bump = ctx.bumps.token_metadata;
+
+        let stream_history = &mut ctx.accounts.stream_history;
+        stream_history.bonding_curve = bonding_curve.key();
+        stream_history.total_live_secs = 0;
+        stream_history.session_count = 0;
+        stream_history.current_session_start = 0;
+        stream_history.next_session_index = 0;
+        stream_history.bump = ctx.bumps.stream_history;
+
+        // Fund the SOL vault, creator fee wallet and holder rewards pool up to
+        // rent exemption so dust-sized trades and fees can never leave them
+        // below the minimum
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        for wallet in [
+            &ctx.accounts.curve_sol_vault,
+            &ctx.accounts.creator_fee_wallet,
+            &ctx.accounts.holder_rewards_pool,
+        ] {
+            let top_up = rent_exempt_minimum.saturating_sub(wallet.lamports());
+            if top_up > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.creator.to_account_info(),
+                            to: wallet.to_account_info(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+        }
 
         // Mint total supply
+        let creator_key = ctx.accounts.creator.key();
         let mint_seeds = &[
             b"token_mint",
-            bonding_curve.creator.as_ref(),
+            creator_key.as_ref(),
             &[ctx.bumps.token_mint],
         ];
         let signer = &[&mint_seeds[..]];
@@ -113,6 +198,116 @@ pub mod zeroglaze {
             CREATOR_SUPPLY,
         )?;
 
+        // Execute the creator's initial buy before anyone else can trade.
+        // It is exempt from launch protection and frozen with the allocation.
+        if let Some(initial_buy_lamports) = initial_buy_lamports {
+            let token_amount = calculate_tokens_for_sol(
+                reserves.virtual_sol_reserves,
+                reserves.virtual_token_reserves,
+                initial_buy_lamports,
+            )?;
+
+            // Priced like buy_tokens, at the first slot after the launch
+            // window since the initial buy is exempt from it. Nobody holds
+            // reward-eligible tokens yet, so there is no holder reward.
+            let BuyQuote {
+                sol_required,
+                platform_fee,
+                creator_fee,
+                total_cost,
+                ..
+            } = compute_buy(
+                bonding_curve,
+                &reserves,
+                &ctx.accounts.platform_state,
+                token_amount,
+                clock.unix_timestamp,
+                bonding_curve.created_slot.saturating_add(bonding_curve.launch_protection_slots),
+                0,
+                false,
+            )?;
+
+            for (to, amount) in [
+                (ctx.accounts.curve_sol_vault.to_account_info(), sol_required),
+                (ctx.accounts.platform_fee_wallet.to_account_info(), platform_fee),
+                (ctx.accounts.creator_fee_wallet.to_account_info(), creator_fee),
+            ] {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.creator.to_account_info(),
+                            to,
+                        },
+                    ),
+                    amount,
+                )?;
+            }
+
+            let curve_seeds = &[
+                b"bonding_curve",
+                bonding_curve.creator.as_ref(),
+                bonding_curve.token_mint.as_ref(),
+                &[bonding_curve.bump],
+            ];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.curve_token_account.to_account_info(),
+                        to: ctx.accounts.creator_token_account.to_account_info(),
+                        authority: bonding_curve.to_account_info(),
+                    },
+                    &[&curve_seeds[..]],
+                ),
+                token_amount,
+            )?;
+
+            // The creator's allocation counts towards the holding cap
+            if let Some(max_holding) = bonding_curve.max_wallet_holding(&reserves) {
+                ctx.accounts.creator_token_account.reload()?;
+                require!(
+                    ctx.accounts.creator_token_account.amount <= max_holding,
+                    ZeroglazeError::HoldingCapExceeded
+                );
+            }
+
+            reserves.record_buy(token_amount, sol_required, total_cost, creator_fee)?;
+            require!(
+                reserves.real_sol_reserves < GRADUATION_THRESHOLD_SOL,
+                ZeroglazeError::InitialBuyTooLarge
+            );
+
+            ctx.accounts.platform_state.record_trade(
+                total_cost,
+                platform_fee + creator_fee,
+                0,
+            )?;
+
+            // Verify bookkeeping against on-chain balances
+            ctx.accounts.curve_token_account.reload()?;
+            check_reserve_invariants(
+                ctx.accounts.curve_sol_vault.lamports(),
+                rent_exempt_minimum,
+                reserves.real_sol_reserves,
+                ctx.accounts.curve_token_account.amount,
+                reserves.real_token_reserves,
+            )?;
+
+            emit!(CreatorInitialBuy {
+                token_mint: bonding_curve.token_mint,
+                creator: bonding_curve.creator,
+                token_amount,
+                sol_amount: sol_required,
+                platform_fee,
+                creator_fee,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Creator initial buy: {} tokens for {} SOL (+ {} fees)",
+                token_amount, sol_required, platform_fee + creator_fee);
+        }
+
         // Freeze creator tokens if requested
         if freeze_creator_allocation {
             token::freeze_account(
@@ -130,11 +325,11 @@ pub mod zeroglaze {
         }
 
         // Update platform state
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.total_tokens_launched += 1;
+        ctx.accounts.platform_state.record_launch()?;
 
-        msg!("Token created: {} ({})", bonding_curve.token_name, bonding_curve.token_symbol);
+        msg!("Token created: {} ({})", token_metadata.token_name, token_metadata.token_symbol);
         msg!("Creator allocation frozen: {}", freeze_creator_allocation);
+        msg!("Launch protection: {} slots", launch_protection_slots);
 
         Ok(())
     }
@@ -145,43 +340,51 @@ pub mod zeroglaze {
         token_amount: u64,
         max_sol_cost: u64,
     ) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-
-        require!(!bonding_curve.graduated, ZeroglazeError::TokenGraduated);
-        require!(token_amount > 0, ZeroglazeError::InvalidAmount);
-        require!(
-            bonding_curve.tokens_sold + token_amount <= CURVE_SUPPLY,
-            ZeroglazeError::InsufficientCurveSupply
-        );
-
-        // Calculate cost using constant product formula
-        let k: u128 = (bonding_curve.virtual_sol_reserves as u128)
-            .checked_mul(bonding_curve.virtual_token_reserves as u128)
-            .ok_or(ZeroglazeError::MathOverflow)?;
-
-        let new_virtual_token_reserves = bonding_curve.virtual_token_reserves
-            .checked_sub(token_amount)
-            .ok_or(ZeroglazeError::MathOverflow)?;
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let mut reserves = ctx.accounts.curve_reserves.load_mut()?;
+        let clock = Clock::get()?;
 
-        let new_virtual_sol_reserves = (k / new_virtual_token_reserves as u128) as u64;
+        let buyer_record = &mut ctx.accounts.buyer_record;
+        if buyer_record.buyer == Pubkey::default() {
+            buyer_record.bonding_curve = bonding_curve.key();
+            buyer_record.buyer = ctx.accounts.buyer.key();
+            buyer_record.bump = ctx.bumps.buyer_record;
+        }
 
-        let sol_required = new_virtual_sol_reserves
-            .checked_sub(bonding_curve.virtual_sol_reserves)
-            .ok_or(ZeroglazeError::MathOverflow)?;
+        // Credit rewards earned on the buyer's existing stake before it changes
+        reserves.settle_holder_rewards(buyer_record, ctx.accounts.buyer_token_account.amount)?;
 
-        // Calculate fees
-        let platform_fee = calculate_fee(sol_required, PLATFORM_FEE_BPS)?;
-        let creator_fee = if bonding_curve.is_live_streaming {
-            calculate_fee(sol_required, CREATOR_FEE_LIVE_BPS)?
-        } else {
-            calculate_fee(sol_required, CREATOR_FEE_OFFLINE_BPS)?
-        };
+        let referred = validate_referrer(
+            ctx.accounts.referrer.as_ref().map(|referrer| referrer.key()),
+            ctx.accounts.referrer_stats.as_ref().map(|stats| stats.referrer),
+            ctx.accounts.buyer.key(),
+        )?;
 
-        let total_cost = sol_required
-            .checked_add(platform_fee)
-            .ok_or(ZeroglazeError::MathOverflow)?
-            .checked_add(creator_fee)
-            .ok_or(ZeroglazeError::MathOverflow)?;
+        // Cost, fees and their split, using the constant product formula
+        let BuyQuote {
+            sol_required,
+            platform_fee,
+            referral_fee,
+            creator_fee,
+            holder_reward,
+            sniper_fee,
+            total_cost,
+            launch_sol_bought,
+            ..
+        } = compute_buy(
+            bonding_curve,
+            &reserves,
+            &ctx.accounts.platform_state,
+            token_amount,
+            clock.unix_timestamp,
+            clock.slot,
+            buyer_record.launch_sol_bought,
+            referred,
+        )?;
+        buyer_record.launch_sol_bought = launch_sol_bought;
+        if sniper_fee > 0 {
+            msg!("Launch sniper fee: {} lamports", sniper_fee);
+        }
 
         require!(total_cost <= max_sol_cost, ZeroglazeError::SlippageExceeded);
 
@@ -206,9 +409,28 @@ pub mod zeroglaze {
                     to: ctx.accounts.platform_fee_wallet.to_account_info(),
                 },
             ),
-            platform_fee,
+            platform_fee - referral_fee,
         )?;
 
+        // Transfer referral fee
+        if let (Some(referrer), Some(referrer_stats)) =
+            (&ctx.accounts.referrer, &mut ctx.accounts.referrer_stats)
+        {
+            if referral_fee > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.buyer.to_account_info(),
+                            to: referrer.to_account_info(),
+                        },
+                    ),
+                    referral_fee,
+                )?;
+            }
+            referrer_stats.record_trade(total_cost, referral_fee)?;
+        }
+
         // Transfer creator fee
         anchor_lang::system_program::transfer(
             CpiContext::new(
@@ -221,6 +443,21 @@ pub mod zeroglaze {
             creator_fee,
         )?;
 
+        // Transfer holder rewards
+        if holder_reward > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to: ctx.accounts.holder_rewards_pool.to_account_info(),
+                    },
+                ),
+                holder_reward,
+            )?;
+            reserves.distribute_holder_rewards(holder_reward)?;
+        }
+
         // Transfer tokens from curve to buyer
         let curve_seeds = &[
             b"bonding_curve",
@@ -236,35 +473,51 @@ pub mod zeroglaze {
                 Transfer {
                     from: ctx.accounts.curve_token_account.to_account_info(),
                     to: ctx.accounts.buyer_token_account.to_account_info(),
-                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                    authority: bonding_curve.to_account_info(),
                 },
                 signer,
             ),
             token_amount,
         )?;
 
+        // Enforce the holding cap on the buyer's post-trade balance
+        if let Some(max_holding) = bonding_curve.max_wallet_holding(&reserves) {
+            ctx.accounts.buyer_token_account.reload()?;
+            require!(
+                ctx.accounts.buyer_token_account.amount <= max_holding,
+                ZeroglazeError::HoldingCapExceeded
+            );
+        }
+
         // Update bonding curve state
-        bonding_curve.virtual_sol_reserves = new_virtual_sol_reserves;
-        bonding_curve.virtual_token_reserves = new_virtual_token_reserves;
-        bonding_curve.real_sol_reserves += sol_required;
-        bonding_curve.real_token_reserves -= token_amount;
-        bonding_curve.tokens_sold += token_amount;
-        bonding_curve.total_volume += total_cost;
-        bonding_curve.creator_fees_collected += creator_fee;
+        reserves.record_buy(token_amount, sol_required, total_cost, creator_fee)?;
+        reserves.add_reward_stake(&mut ctx.accounts.buyer_record, token_amount)?;
 
         // Update platform state
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.total_volume_sol += total_cost;
-        platform_state.total_fees_collected += platform_fee + creator_fee;
+        ctx.accounts.platform_state.record_trade(
+            total_cost,
+            platform_fee + creator_fee + holder_reward,
+            referral_fee,
+        )?;
 
         // Check for graduation
-        if bonding_curve.real_sol_reserves >= GRADUATION_THRESHOLD_SOL {
-            bonding_curve.graduated = true;
+        if reserves.real_sol_reserves >= GRADUATION_THRESHOLD_SOL {
+            reserves.graduate(clock.unix_timestamp);
             msg!("Token graduated to DEX!");
         }
 
+        // Verify bookkeeping against on-chain balances
+        ctx.accounts.curve_token_account.reload()?;
+        check_reserve_invariants(
+            ctx.accounts.curve_sol_vault.lamports(),
+            Rent::get()?.minimum_balance(ctx.accounts.curve_sol_vault.data_len()),
+            reserves.real_sol_reserves,
+            ctx.accounts.curve_token_account.amount,
+            reserves.real_token_reserves,
+        )?;
+
         msg!("Buy executed: {} tokens for {} SOL (+ {} fees)",
-            token_amount, sol_required, platform_fee + creator_fee);
+            token_amount, sol_required, platform_fee + creator_fee + holder_reward);
 
         Ok(())
     }
@@ -275,45 +528,46 @@ pub mod zeroglaze {
         token_amount: u64,
         min_sol_output: u64,
     ) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
-
-        require!(!bonding_curve.graduated, ZeroglazeError::TokenGraduated);
-        require!(token_amount > 0, ZeroglazeError::InvalidAmount);
-
-        // Calculate SOL to return using constant product formula
-        let k: u128 = (bonding_curve.virtual_sol_reserves as u128)
-            .checked_mul(bonding_curve.virtual_token_reserves as u128)
-            .ok_or(ZeroglazeError::MathOverflow)?;
-
-        let new_virtual_token_reserves = bonding_curve.virtual_token_reserves
-            .checked_add(token_amount)
-            .ok_or(ZeroglazeError::MathOverflow)?;
-
-        let new_virtual_sol_reserves = (k / new_virtual_token_reserves as u128) as u64;
-
-        let sol_to_return = bonding_curve.virtual_sol_reserves
-            .checked_sub(new_virtual_sol_reserves)
-            .ok_or(ZeroglazeError::MathOverflow)?;
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let mut reserves = ctx.accounts.curve_reserves.load_mut()?;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Settle the seller's rewards and drop the sold tokens from their stake
+        // so the live bonus below goes to the remaining holders
+        let seller_record = &mut ctx.accounts.seller_record;
+        if seller_record.buyer == Pubkey::default() {
+            seller_record.bonding_curve = bonding_curve.key();
+            seller_record.buyer = ctx.accounts.seller.key();
+            seller_record.bump = ctx.bumps.seller_record;
+        }
+        reserves.settle_holder_rewards(seller_record, ctx.accounts.seller_token_account.amount)?;
+        reserves.remove_reward_stake(seller_record, token_amount)?;
 
-        // Calculate fees
-        let platform_fee = calculate_fee(sol_to_return, PLATFORM_FEE_BPS)?;
-        let creator_fee = if bonding_curve.is_live_streaming {
-            calculate_fee(sol_to_return, CREATOR_FEE_LIVE_BPS)?
-        } else {
-            calculate_fee(sol_to_return, CREATOR_FEE_OFFLINE_BPS)?
-        };
+        let referred = validate_referrer(
+            ctx.accounts.referrer.as_ref().map(|referrer| referrer.key()),
+            ctx.accounts.referrer_stats.as_ref().map(|stats| stats.referrer),
+            ctx.accounts.seller.key(),
+        )?;
 
-        let net_sol_output = sol_to_return
-            .checked_sub(platform_fee)
-            .ok_or(ZeroglazeError::MathOverflow)?
-            .checked_sub(creator_fee)
-            .ok_or(ZeroglazeError::MathOverflow)?;
+        // SOL to return, fees and their split, using the constant product formula
+        let SellQuote {
+            sol_to_return,
+            platform_fee,
+            referral_fee,
+            creator_fee,
+            holder_reward,
+            net_output: net_sol_output,
+            ..
+        } = compute_sell(
+            bonding_curve,
+            &reserves,
+            &ctx.accounts.platform_state,
+            token_amount,
+            now,
+            referred,
+        )?;
 
         require!(net_sol_output >= min_sol_output, ZeroglazeError::SlippageExceeded);
-        require!(
-            bonding_curve.real_sol_reserves >= sol_to_return,
-            ZeroglazeError::InsufficientLiquidity
-        );
 
         // Transfer tokens from seller to curve
         token::transfer(
@@ -328,41 +582,112 @@ pub mod zeroglaze {
             token_amount,
         )?;
 
-        // Transfer SOL from curve to seller
-        let curve_seeds = &[
-            b"bonding_curve",
-            bonding_curve.creator.as_ref(),
-            bonding_curve.token_mint.as_ref(),
-            &[bonding_curve.bump],
+        // Transfer SOL out of the vault, signing with its PDA seeds
+        let bonding_curve_key = bonding_curve.key();
+        let vault_seeds = &[
+            b"curve_sol_vault",
+            bonding_curve_key.as_ref(),
+            &[ctx.bumps.curve_sol_vault],
         ];
-        let signer = &[&curve_seeds[..]];
+        let signer = &[&vault_seeds[..]];
+
+        // Transfer SOL from curve to seller
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.curve_sol_vault.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+                signer,
+            ),
+            net_sol_output,
+        )?;
+
+        // Transfer platform fee
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.curve_sol_vault.to_account_info(),
+                    to: ctx.accounts.platform_fee_wallet.to_account_info(),
+                },
+                signer,
+            ),
+            platform_fee - referral_fee,
+        )?;
 
-        **ctx.accounts.curve_sol_vault.to_account_info().try_borrow_mut_lamports()? -= net_sol_output;
-        **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += net_sol_output;
+        // Transfer referral fee
+        if let (Some(referrer), Some(referrer_stats)) =
+            (&ctx.accounts.referrer, &mut ctx.accounts.referrer_stats)
+        {
+            if referral_fee > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.curve_sol_vault.to_account_info(),
+                            to: referrer.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    referral_fee,
+                )?;
+            }
+            referrer_stats.record_trade(sol_to_return, referral_fee)?;
+        }
 
-        // Transfer fees
-        **ctx.accounts.curve_sol_vault.to_account_info().try_borrow_mut_lamports()? -= platform_fee;
-        **ctx.accounts.platform_fee_wallet.to_account_info().try_borrow_mut_lamports()? += platform_fee;
+        // Transfer creator fee
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.curve_sol_vault.to_account_info(),
+                    to: ctx.accounts.creator_fee_wallet.to_account_info(),
+                },
+                signer,
+            ),
+            creator_fee,
+        )?;
 
-        **ctx.accounts.curve_sol_vault.to_account_info().try_borrow_mut_lamports()? -= creator_fee;
-        **ctx.accounts.creator_fee_wallet.to_account_info().try_borrow_mut_lamports()? += creator_fee;
+        // Transfer holder rewards
+        if holder_reward > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.curve_sol_vault.to_account_info(),
+                        to: ctx.accounts.holder_rewards_pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                holder_reward,
+            )?;
+            reserves.distribute_holder_rewards(holder_reward)?;
+        }
 
         // Update bonding curve state
-        bonding_curve.virtual_sol_reserves = new_virtual_sol_reserves;
-        bonding_curve.virtual_token_reserves = new_virtual_token_reserves;
-        bonding_curve.real_sol_reserves -= sol_to_return;
-        bonding_curve.real_token_reserves += token_amount;
-        bonding_curve.tokens_sold -= token_amount;
-        bonding_curve.total_volume += sol_to_return;
-        bonding_curve.creator_fees_collected += creator_fee;
+        reserves.record_sell(token_amount, sol_to_return, creator_fee)?;
 
         // Update platform state
-        let platform_state = &mut ctx.accounts.platform_state;
-        platform_state.total_volume_sol += sol_to_return;
-        platform_state.total_fees_collected += platform_fee + creator_fee;
+        ctx.accounts.platform_state.record_trade(
+            sol_to_return,
+            platform_fee + creator_fee + holder_reward,
+            referral_fee,
+        )?;
+
+        // Verify bookkeeping against on-chain balances
+        ctx.accounts.curve_token_account.reload()?;
+        check_reserve_invariants(
+            ctx.accounts.curve_sol_vault.lamports(),
+            Rent::get()?.minimum_balance(ctx.accounts.curve_sol_vault.data_len()),
+            reserves.real_sol_reserves,
+            ctx.accounts.curve_token_account.amount,
+            reserves.real_token_reserves,
+        )?;
 
         msg!("Sell executed: {} tokens for {} SOL (- {} fees)",
-            token_amount, sol_to_return, platform_fee + creator_fee);
+            token_amount, sol_to_return, platform_fee + creator_fee + holder_reward);
 
         Ok(())
     }
@@ -372,250 +697,1585 @@ pub mod zeroglaze {
         ctx: Context<UpdateStreamStatus>,
         is_live: bool,
     ) -> Result<()> {
-        let bonding_curve = &mut ctx.accounts.bonding_curve;
         let clock = Clock::get()?;
 
-        bonding_curve.is_live_streaming = is_live;
-        bonding_curve.last_stream_check = clock.unix_timestamp;
+        ctx.accounts.bonding_curve.set_stream_status(
+            &mut ctx.accounts.stream_history,
+            is_live,
+            clock.unix_timestamp,
+            ctx.accounts.platform_state.max_stream_staleness_secs,
+        )?;
 
         msg!("Stream status updated: {}", if is_live { "LIVE" } else { "OFFLINE" });
 
         Ok(())
     }
 
-    /// Withdraw creator fees (only creator can call)
-    pub fn withdraw_creator_fees(
-        ctx: Context<WithdrawCreatorFees>,
+    /// Update stream status from an oracle attestation (anyone can relay)
+    ///
+    /// The transaction must include an Ed25519 program instruction, directly
+    /// before this one, verifying the oracle's signature over the
+    /// Borsh-serialized attestation.
+    pub fn relay_stream_attestation(
+        ctx: Context<RelayStreamAttestation>,
+        attestation: StreamAttestation,
     ) -> Result<()> {
-        let bonding_curve = &ctx.accounts.bonding_curve;
-        let available_balance = ctx.accounts.creator_fee_wallet.lamports();
+        let platform_state = &ctx.accounts.platform_state;
+        require_keys_neq!(
+            platform_state.stream_oracle,
+            Pubkey::default(),
+            ZeroglazeError::OracleNotConfigured
+        );
+
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current_index = instructions_sysvar::load_current_index_checked(&instructions)?;
+        require!(current_index > 0, ZeroglazeError::InvalidAttestation);
+        let ed25519_ix = instructions_sysvar::load_instruction_at_checked(
+            current_index as usize - 1,
+            &instructions,
+        )?;
+        verify_ed25519_instruction(
+            &ed25519_ix,
+            &platform_state.stream_oracle,
+            &attestation.try_to_vec()?,
+        )?;
+
+        let bonding_curve = &mut ctx.accounts.bonding_curve;
+        require_keys_eq!(
+            attestation.token_mint,
+            bonding_curve.token_mint,
+            ZeroglazeError::InvalidAttestation
+        );
+        require!(
+            attestation.nonce > bonding_curve.last_attestation_nonce,
+            ZeroglazeError::AttestationReplayed
+        );
 
-        require!(available_balance > 0, ZeroglazeError::NoFeesToWithdraw);
+        // Never let an older observation override a newer update
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            attestation.timestamp >= bonding_curve.last_stream_check
+                && now.saturating_sub(attestation.timestamp) <= platform_state.max_stream_staleness_secs,
+            ZeroglazeError::StaleAttestation
+        );
 
-        // Transfer all available fees to creator
-        **ctx.accounts.creator_fee_wallet.to_account_info().try_borrow_mut_lamports()? -= available_balance;
-        **ctx.accounts.creator.to_account_info().try_borrow_mut_lamports()? += available_balance;
+        bonding_curve.set_stream_status(
+            &mut ctx.accounts.stream_history,
+            attestation.is_live,
+            attestation.timestamp.min(now),
+            platform_state.max_stream_staleness_secs,
+        )?;
+        bonding_curve.last_attestation_nonce = attestation.nonce;
 
-        msg!("Creator withdrew {} SOL in fees", available_balance as f64 / 1e9);
+        msg!("Stream status attested: {}", if attestation.is_live { "LIVE" } else { "OFFLINE" });
 
         Ok(())
     }
-}
 
-// ============================================================================
-// Context Structs
-// ============================================================================
+    /// Update the keeper-reported stream tier (e.g. viewer bucket) that
+    /// selects the live creator fee
+    pub fn update_stream_tier(
+        ctx: Context<UpdateStreamStatus>,
+        live_fee_tier: u8,
+    ) -> Result<()> {
+        require!(
+            (live_fee_tier as usize) < LIVE_FEE_TIER_COUNT,
+            ZeroglazeError::InvalidLiveFeeTier
+        );
 
-#[derive(Accounts)]
-pub struct InitializePlatform<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + PlatformState::INIT_SPACE,
-        seeds = [b"platform_state"],
-        bump
-    )]
-    pub platform_state: Account<'info, PlatformState>,
+        ctx.accounts.bonding_curve.live_fee_tier = live_fee_tier;
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        msg!("Stream tier updated: {}", live_fee_tier);
 
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct CreateToken<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + BondingCurve::INIT_SPACE,
-        seeds = [b"bonding_curve", creator.key().as_ref(), token_mint.key().as_ref()],
-        bump
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    /// Update stream status for many tokens at once (each token's bonding
+    /// curve and stream history passed as a pair of remaining accounts, in the
+    /// same order as `statuses`)
+    pub fn batch_update_stream_status<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchUpdateStreamStatus<'info>>,
+        statuses: Vec<bool>,
+    ) -> Result<()> {
+        require!(
+            statuses.len().checked_mul(2) == Some(ctx.remaining_accounts.len()),
+            ZeroglazeError::StreamBatchLengthMismatch
+        );
 
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = 6,
-        mint::authority = token_mint,
-        mint::freeze_authority = token_mint,
-        seeds = [b"token_mint", creator.key().as_ref()],
-        bump
-    )]
-    pub token_mint: Account<'info, Mint>,
+        let now = Clock::get()?.unix_timestamp;
+        // Unchanged tokens are skipped, but still refreshed before they go stale
+        let max_staleness_secs = ctx.accounts.platform_state.max_stream_staleness_secs;
+        let refresh_after = max_staleness_secs / 2;
+        let mut updated = 0;
+
+        for (accounts, &is_live) in ctx.remaining_accounts.chunks_exact(2).zip(statuses.iter()) {
+            let (account_info, history_info) = (&accounts[0], &accounts[1]);
+            let mut bonding_curve: Account<BondingCurve> = Account::try_from(account_info)?;
+
+            let expected_address = Pubkey::create_program_address(
+                &[
+                    b"bonding_curve",
+                    bonding_curve.creator.as_ref(),
+                    bonding_curve.token_mint.as_ref(),
+                    &[bonding_curve.bump],
+                ],
+                ctx.program_id,
+            )
+            .map_err(|_| ZeroglazeError::InvalidBondingCurve)?;
+            require_keys_eq!(
+                expected_address,
+                account_info.key(),
+                ZeroglazeError::InvalidBondingCurve
+            );
+
+            if bonding_curve.is_live_streaming == is_live
+                && now.saturating_sub(bonding_curve.last_stream_check) < refresh_after
+            {
+                continue;
+            }
+
+            let mut stream_history: Account<StreamHistory> = Account::try_from(history_info)?;
+            require_keys_eq!(
+                stream_history.bonding_curve,
+                account_info.key(),
+                ZeroglazeError::InvalidBondingCurve
+            );
+            bonding_curve.set_stream_status(&mut stream_history, is_live, now, max_staleness_secs)?;
+            stream_history.exit(ctx.program_id)?;
+            bonding_curve.exit(ctx.program_id)?;
+            updated += 1;
+        }
 
-    #[account(
-        init,
-        payer = creator,
-        associated_token::mint = token_mint,
-        associated_token::authority = bonding_curve,
-    )]
-    pub curve_token_account: Account<'info, TokenAccount>,
+        msg!("Stream status batch: {} of {} tokens updated", updated, statuses.len());
 
-    #[account(
-        init,
-        payer = creator,
-        associated_token::mint = token_mint,
-        associated_token::authority = creator,
-    )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+        Ok(())
+    }
 
-    #[account(
-        mut,
-        seeds = [b"platform_state"],
-        bump = platform_state.bump
-    )]
-    pub platform_state: Account<'info, PlatformState>,
+    /// Refresh the stream status timestamp without changing it (keeper liveness)
+    pub fn stream_heartbeat(
+        ctx: Context<UpdateStreamStatus>,
+    ) -> Result<()> {
+        let bonding_curve = &mut ctx.accounts.bonding_curve;
+        let now = Clock::get()?.unix_timestamp;
+
+        let is_live = bonding_curve.is_live_streaming;
+        bonding_curve.set_stream_status(
+            &mut ctx.accounts.stream_history,
+            is_live,
+            now,
+            ctx.accounts.platform_state.max_stream_staleness_secs,
+        )?;
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        msg!("Stream heartbeat: {}", if bonding_curve.is_live_streaming { "LIVE" } else { "OFFLINE" });
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct BuyTokens<'info> {
-    #[account(
-        mut,
-        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
-        bump = bonding_curve.bump
-    )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    /// Set how long stream status stays valid without a keeper update
+    pub fn set_max_stream_staleness(
+        ctx: Context<UpdatePlatformConfig>,
+        max_stream_staleness_secs: i64,
+    ) -> Result<()> {
+        require!(max_stream_staleness_secs > 0, ZeroglazeError::InvalidStreamStaleness);
 
-    #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
+        ctx.accounts.platform_state.max_stream_staleness_secs = max_stream_staleness_secs;
 
-    #[account(
-        mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = bonding_curve,
-    )]
-    pub curve_token_account: Account<'info, TokenAccount>,
+        msg!("Max stream staleness set to {} seconds", max_stream_staleness_secs);
 
-    #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = token_mint,
-        associated_token::authority = buyer,
-    )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+        Ok(())
+    }
+
+    /// Set the creator fee charged at each live stream tier
+    pub fn set_live_fee_tiers(
+        ctx: Context<UpdatePlatformConfig>,
+        live_fee_tiers_bps: [u16; LIVE_FEE_TIER_COUNT],
+    ) -> Result<()> {
+        require!(
+            live_fee_tiers_bps[0] >= CREATOR_FEE_OFFLINE_BPS
+                && live_fee_tiers_bps[LIVE_FEE_TIER_COUNT - 1] <= MAX_CREATOR_FEE_BPS
+                && live_fee_tiers_bps.windows(2).all(|pair| pair[0] <= pair[1]),
+            ZeroglazeError::InvalidLiveFeeTiers
+        );
+
+        ctx.accounts.platform_state.live_fee_tiers_bps = live_fee_tiers_bps;
+
+        msg!("Live fee tiers set to {:?} bps", live_fee_tiers_bps);
+
+        Ok(())
+    }
+
+    /// Set the share of the live creator fee paid to token holders
+    pub fn set_holder_reward_share(
+        ctx: Context<UpdatePlatformConfig>,
+        holder_reward_share_bps: u16,
+    ) -> Result<()> {
+        require!(
+            holder_reward_share_bps as u64 <= BPS_DENOMINATOR,
+            ZeroglazeError::InvalidHolderRewardShare
+        );
+
+        ctx.accounts.platform_state.holder_reward_share_bps = holder_reward_share_bps;
+
+        msg!("Holder reward share set to {} bps", holder_reward_share_bps);
+
+        Ok(())
+    }
+
+    /// Set the share of the platform fee paid to referrers
+    pub fn set_referral_fee(
+        ctx: Context<UpdatePlatformConfig>,
+        referral_fee_bps: u16,
+    ) -> Result<()> {
+        require!(
+            referral_fee_bps as u64 <= BPS_DENOMINATOR,
+            ZeroglazeError::InvalidReferralFee
+        );
+
+        ctx.accounts.platform_state.referral_fee_bps = referral_fee_bps;
+
+        msg!("Referral fee set to {} bps of the platform fee", referral_fee_bps);
+
+        Ok(())
+    }
+
+    /// Register the oracle key whose stream attestations are accepted
+    /// (Pubkey::default() disables attestations)
+    pub fn set_stream_oracle(
+        ctx: Context<UpdatePlatformConfig>,
+        stream_oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.platform_state.stream_oracle = stream_oracle;
+
+        msg!("Stream oracle set to {}", stream_oracle);
+
+        Ok(())
+    }
+
+    /// Register as a referrer so trades can pay out referral fees
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
+    ) -> Result<()> {
+        let referrer_stats = &mut ctx.accounts.referrer_stats;
+        referrer_stats.referrer = ctx.accounts.referrer.key();
+        referrer_stats.total_fees_earned = 0;
+        referrer_stats.total_volume_referred = 0;
+        referrer_stats.trade_count = 0;
+        referrer_stats.bump = ctx.bumps.referrer_stats;
+
+        msg!("Referrer registered: {}", ctx.accounts.referrer.key());
+
+        Ok(())
+    }
+
+    /// Claim holder rewards accrued from the live bonus
+    pub fn claim_holder_rewards(
+        ctx: Context<ClaimHolderRewards>,
+    ) -> Result<()> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let holder_record = &mut ctx.accounts.holder_record;
+        let amount = {
+            let mut reserves = ctx.accounts.curve_reserves.load_mut()?;
+            reserves.settle_holder_rewards(holder_record, ctx.accounts.holder_token_account.amount)?;
+            reserves.claim_holder_rewards(holder_record)?
+        };
+
+        let bonding_curve_key = bonding_curve.key();
+        let pool_seeds = &[
+            b"holder_rewards_pool",
+            bonding_curve_key.as_ref(),
+            &[ctx.bumps.holder_rewards_pool],
+        ];
+        let signer = &[&pool_seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.holder_rewards_pool.to_account_info(),
+                    to: ctx.accounts.holder.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(HolderRewardsClaimed {
+            token_mint: bonding_curve.token_mint,
+            holder: ctx.accounts.holder.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Holder claimed {} lamports in rewards", amount);
+
+        Ok(())
+    }
+
+    /// Replace the creator fee split; recipients whose share shrinks must
+    /// co-sign (passed as remaining accounts)
+    ///
+    /// This includes the original creator wallet once the fee authority has
+    /// moved, so a new fee authority cannot write the creator out of the split.
+    pub fn update_fee_recipients(
+        ctx: Context<UpdateFeeRecipients>,
+        fee_recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        validate_fee_recipients(&fee_recipients)?;
+
+        let bonding_curve = &mut ctx.accounts.bonding_curve;
+        let fee_authority = ctx.accounts.fee_authority.key();
+        for current in bonding_curve.fee_recipients.iter() {
+            let new_weight_bps = fee_recipients
+                .iter()
+                .find(|recipient| recipient.wallet == current.wallet)
+                .map_or(0, |recipient| recipient.weight_bps);
+            if new_weight_bps < current.weight_bps {
+                let consented = current.wallet == fee_authority
+                    || ctx.remaining_accounts
+                        .iter()
+                        .any(|account| account.key() == current.wallet && account.is_signer);
+                require!(consented, ZeroglazeError::MissingRecipientConsent);
+            }
+        }
+
+        bonding_curve.fee_recipients = fee_recipients;
+
+        msg!("Creator fee split updated: {} recipients", bonding_curve.fee_recipients.len());
+
+        Ok(())
+    }
+
+    /// Withdraw creator fees (only the fee authority can call), split among
+    /// the fee recipients if any are set
+    ///
+    /// `amount` defaults to everything still pending. The fee authority's
+    /// share goes to `destination` when one is passed; passing one when the
+    /// fee authority has no share is rejected. Recipient wallets are passed as
+    /// remaining accounts in split order.
+    pub fn withdraw_creator_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawCreatorFees<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let rent_exempt_minimum = Rent::get()?
+            .minimum_balance(ctx.accounts.creator_fee_wallet.data_len());
+        let available_balance = ctx.accounts.creator_fee_wallet
+            .lamports()
+            .saturating_sub(rent_exempt_minimum);
+
+        // Only fees recorded on the curve are withdrawable, so the PDA balance
+        // always covers creator_fees_collected - creator_fees_withdrawn
+        let mut reserves = ctx.accounts.curve_reserves.load_mut()?;
+        let withdrawable = reserves.creator_fees_pending().min(available_balance);
+        require!(withdrawable > 0, ZeroglazeError::NoFeesToWithdraw);
+
+        let amount = amount.unwrap_or(withdrawable);
+        require!(amount > 0, ZeroglazeError::InvalidAmount);
+        require!(amount <= withdrawable, ZeroglazeError::InsufficientCreatorFees);
+
+        let authority_payee = match &ctx.accounts.destination {
+            Some(destination) => destination.to_account_info(),
+            None => ctx.accounts.fee_authority.to_account_info(),
+        };
+
+        let payouts = if bonding_curve.fee_recipients.is_empty() {
+            vec![(authority_payee.clone(), amount)]
+        } else {
+            require!(
+                ctx.remaining_accounts.len() == bonding_curve.fee_recipients.len(),
+                ZeroglazeError::InvalidFeeRecipients
+            );
+            let shares = split_by_weight(amount, &bonding_curve.fee_recipients);
+            let mut payouts = Vec::with_capacity(shares.len());
+            for ((recipient, wallet), share) in bonding_curve.fee_recipients
+                .iter()
+                .zip(ctx.remaining_accounts.iter())
+                .zip(shares)
+            {
+                require_keys_eq!(wallet.key(), recipient.wallet, ZeroglazeError::InvalidFeeRecipients);
+                if recipient.wallet == bonding_curve.fee_authority {
+                    payouts.push((authority_payee.clone(), share));
+                } else {
+                    payouts.push((wallet.clone(), share));
+                }
+            }
+            require!(
+                ctx.accounts.destination.is_none()
+                    || bonding_curve.fee_recipients.iter().any(|r| r.wallet == bonding_curve.fee_authority),
+                ZeroglazeError::UnusedDestination
+            );
+            payouts
+        };
+
+        let fee_wallet_seeds = &[
+            b"creator_fee_wallet",
+            bonding_curve.creator.as_ref(),
+            bonding_curve.token_mint.as_ref(),
+            &[ctx.bumps.creator_fee_wallet],
+        ];
+        let signer = &[&fee_wallet_seeds[..]];
+
+        let mut paid = Vec::with_capacity(payouts.len());
+        for (to, share) in payouts {
+            if share == 0 {
+                continue;
+            }
+            paid.push(FeePayout { wallet: to.key(), amount: share });
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator_fee_wallet.to_account_info(),
+                        to,
+                    },
+                    signer,
+                ),
+                share,
+            )?;
+        }
+
+        reserves.creator_fees_withdrawn = reserves.creator_fees_withdrawn
+            .checked_add(amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+
+        emit!(CreatorFeesWithdrawn {
+            token_mint: bonding_curve.token_mint,
+            creator: bonding_curve.creator,
+            payouts: paid,
+            amount,
+            remaining: reserves.creator_fees_pending(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Creator withdrew {} SOL in fees", amount as f64 / 1e9);
+
+        Ok(())
+    }
+
+    /// Move a graduated curve's liquidity out for the DEX pool (platform
+    /// authority only)
+    ///
+    /// The platform keeps `GRADUATION_FEE_SOL` of the raised SOL. The rest, and
+    /// every token left on the curve, go to the token's `dex_liquidity` PDA
+    /// and its token account, where only the program can move them.
+    pub fn withdraw_graduated_liquidity(
+        ctx: Context<WithdrawGraduatedLiquidity>,
+    ) -> Result<()> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let mut reserves = ctx.accounts.curve_reserves.load_mut()?;
+        require!(reserves.is_graduated(), ZeroglazeError::TokenNotGraduated);
+
+        let token_amount = ctx.accounts.curve_token_account.amount;
+        require!(
+            reserves.real_sol_reserves > 0 || token_amount > 0,
+            ZeroglazeError::LiquidityAlreadyWithdrawn
+        );
+        let graduation_fee = GRADUATION_FEE_SOL.min(reserves.real_sol_reserves);
+        let sol_amount = reserves.real_sol_reserves - graduation_fee;
+
+        let bonding_curve_key = bonding_curve.key();
+        let vault_seeds = &[
+            b"curve_sol_vault",
+            bonding_curve_key.as_ref(),
+            &[ctx.bumps.curve_sol_vault],
+        ];
+        for (to, amount) in [
+            (ctx.accounts.platform_fee_wallet.to_account_info(), graduation_fee),
+            (ctx.accounts.dex_liquidity.to_account_info(), sol_amount),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.curve_sol_vault.to_account_info(),
+                        to,
+                    },
+                    &[&vault_seeds[..]],
+                ),
+                amount,
+            )?;
+        }
+
+        if token_amount > 0 {
+            let curve_seeds = &[
+                b"bonding_curve",
+                bonding_curve.creator.as_ref(),
+                bonding_curve.token_mint.as_ref(),
+                &[bonding_curve.bump],
+            ];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.curve_token_account.to_account_info(),
+                        to: ctx.accounts.dex_liquidity_token_account.to_account_info(),
+                        authority: bonding_curve.to_account_info(),
+                    },
+                    &[&curve_seeds[..]],
+                ),
+                token_amount,
+            )?;
+        }
+
+        reserves.real_sol_reserves = 0;
+        reserves.real_token_reserves = 0;
+
+        let platform_state = &mut ctx.accounts.platform_state;
+        platform_state.total_fees_collected = platform_state.total_fees_collected
+            .checked_add(graduation_fee)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+
+        emit!(GraduatedLiquidityWithdrawn {
+            token_mint: bonding_curve.token_mint,
+            sol_amount,
+            token_amount,
+            graduation_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Graduated liquidity withdrawn: {} lamports, {} tokens", sol_amount, token_amount);
+
+        Ok(())
+    }
+
+    /// Close a graduated, fully drained curve: its token account, SOL PDAs,
+    /// reserves, metadata and stream history are closed back to the creator
+    /// and a GraduatedToken record is kept for history
+    pub fn close_curve(
+        ctx: Context<CloseCurve>,
+    ) -> Result<()> {
+        let bonding_curve = &ctx.accounts.bonding_curve;
+        let reserves = *ctx.accounts.curve_reserves.load()?;
+        require!(reserves.is_graduated(), ZeroglazeError::TokenNotGraduated);
+
+        // Rewards still owed to holders block the close until the claim
+        // window has passed; forfeited rewards and rounding dust don't
+        let clock = Clock::get()?;
+        require!(
+            ctx.accounts.curve_token_account.amount == 0
+                && reserves.real_sol_reserves == 0
+                && reserves.creator_fees_pending() == 0
+                && (reserves.holder_rewards_outstanding() <= MAX_CLOSE_REWARD_DUST_LAMPORTS
+                    || !reserves.holder_reward_claims_open(clock.unix_timestamp)),
+            ZeroglazeError::CurveNotDrained
+        );
+
+        // Whatever is left in the rewards pool goes to the platform
+        let rent = Rent::get()?;
+        let pool_rent = rent.minimum_balance(ctx.accounts.holder_rewards_pool.data_len());
+        let unclaimed_rewards = ctx.accounts.holder_rewards_pool.lamports().saturating_sub(pool_rent);
+
+        let graduated_token = &mut ctx.accounts.graduated_token;
+        graduated_token.token_mint = bonding_curve.token_mint;
+        graduated_token.creator = bonding_curve.creator;
+        graduated_token.token_symbol = ctx.accounts.token_metadata.token_symbol.clone();
+        graduated_token.tokens_sold = reserves.tokens_sold;
+        graduated_token.total_volume = reserves.total_volume;
+        graduated_token.creator_fees_collected = reserves.creator_fees_collected;
+        graduated_token.holder_rewards_distributed = reserves.holder_rewards_distributed;
+        graduated_token.total_live_secs = ctx.accounts.stream_history.total_live_secs;
+        graduated_token.closed_at = clock.unix_timestamp;
+        graduated_token.bump = ctx.bumps.graduated_token;
+
+        let bonding_curve_key = bonding_curve.key();
+        let pool_seeds: &[&[u8]] = &[
+            b"holder_rewards_pool",
+            bonding_curve_key.as_ref(),
+            &[ctx.bumps.holder_rewards_pool],
+        ];
+        if unclaimed_rewards > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.holder_rewards_pool.to_account_info(),
+                        to: ctx.accounts.platform_fee_wallet.to_account_info(),
+                    },
+                    &[pool_seeds],
+                ),
+                unclaimed_rewards,
+            )?;
+        }
+
+        let creator_before = ctx.accounts.creator.lamports();
+
+        // Close the curve's token account
+        let curve_seeds = &[
+            b"bonding_curve",
+            bonding_curve.creator.as_ref(),
+            bonding_curve.token_mint.as_ref(),
+            &[bonding_curve.bump],
+        ];
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: ctx.accounts.curve_token_account.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: bonding_curve.to_account_info(),
+            },
+            &[&curve_seeds[..]],
+        ))?;
+
+        // Empty the system-owned PDAs; with no lamports left they are reaped
+        let vault_seeds: &[&[u8]] = &[
+            b"curve_sol_vault",
+            bonding_curve_key.as_ref(),
+            &[ctx.bumps.curve_sol_vault],
+        ];
+        let fee_wallet_seeds: &[&[u8]] = &[
+            b"creator_fee_wallet",
+            bonding_curve.creator.as_ref(),
+            bonding_curve.token_mint.as_ref(),
+            &[ctx.bumps.creator_fee_wallet],
+        ];
+        for (wallet, seeds) in [
+            (ctx.accounts.curve_sol_vault.to_account_info(), vault_seeds),
+            (ctx.accounts.creator_fee_wallet.to_account_info(), fee_wallet_seeds),
+            (ctx.accounts.holder_rewards_pool.to_account_info(), pool_seeds),
+        ] {
+            let lamports = wallet.lamports();
+            if lamports == 0 {
+                continue;
+            }
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: wallet,
+                        to: ctx.accounts.creator.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                lamports,
+            )?;
+        }
+
+        // The curve's program accounts are closed by Anchor on exit
+        let reclaimed_lamports = ctx.accounts.creator.lamports() - creator_before
+            + ctx.accounts.bonding_curve.to_account_info().lamports()
+            + ctx.accounts.curve_reserves.to_account_info().lamports()
+            + ctx.accounts.token_metadata.to_account_info().lamports()
+            + ctx.accounts.stream_history.to_account_info().lamports();
+
+        emit!(CurveClosed {
+            token_mint: bonding_curve.token_mint,
+            creator: bonding_curve.creator,
+            reclaimed_lamports,
+            forfeited_rewards: unclaimed_rewards,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Curve closed, {} lamports returned to creator", reclaimed_lamports);
+
+        Ok(())
+    }
+
+    /// Rewrite a platform state created before account versioning onto the
+    /// current layout, growing the account as needed
+    pub fn migrate_platform(
+        ctx: Context<MigratePlatform>,
+    ) -> Result<()> {
+        let account = ctx.accounts.platform_state.to_account_info();
+        let platform_state = {
+            let data = account.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == PlatformState::DISCRIMINATOR,
+                ZeroglazeError::InvariantViolation
+            );
+            // Same scheme as migrate_curve: only V0 is told apart by size
+            if data.len() != 8 + legacy::PlatformStateV0::INIT_SPACE {
+                let version = PlatformState::try_deserialize(&mut &data[..])
+                    .map_err(|_| ZeroglazeError::UnsupportedAccountVersion)?
+                    .version;
+                require!(version != PLATFORM_STATE_VERSION, ZeroglazeError::AccountAlreadyMigrated);
+                return err!(ZeroglazeError::UnsupportedAccountVersion);
+            }
+            PlatformState::from(legacy::PlatformStateV0::deserialize(&mut &data[8..])?)
+        };
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            platform_state.authority,
+            ZeroglazeError::Unauthorized
+        );
+
+        realloc_with_rent(
+            &account,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + PlatformState::INIT_SPACE,
+        )?;
+        let mut data = account.try_borrow_mut_data()?;
+        PlatformState { version: PLATFORM_STATE_VERSION, ..platform_state }
+            .try_serialize(&mut &mut data[..])?;
+
+        msg!("Platform state migrated to v{}", PLATFORM_STATE_VERSION);
+
+        Ok(())
+    }
+
+    /// Rewrite a bonding curve from the pre-versioning layout onto the current
+    /// one, creating its reserves, metadata and stream history accounts
+    /// (anyone may pay)
+    pub fn migrate_curve(
+        ctx: Context<MigrateCurve>,
+    ) -> Result<()> {
+        let account = ctx.accounts.bonding_curve.to_account_info();
+        let legacy_curve = {
+            let data = account.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == BondingCurve::DISCRIMINATOR,
+                ZeroglazeError::InvalidBondingCurve
+            );
+            // V0 predates the version field and is the only layout told
+            // apart by size; every later layout says which version it is
+            if data.len() != 8 + legacy::BondingCurveV0::INIT_SPACE {
+                let version = BondingCurve::try_deserialize(&mut &data[..])
+                    .map_err(|_| ZeroglazeError::UnsupportedAccountVersion)?
+                    .version;
+                require!(version != BONDING_CURVE_VERSION, ZeroglazeError::AccountAlreadyMigrated);
+                return err!(ZeroglazeError::UnsupportedAccountVersion);
+            }
+            legacy::BondingCurveV0::deserialize(&mut &data[8..])?
+        };
+
+        // Only the curve PDA itself may be migrated
+        let expected = Pubkey::create_program_address(
+            &[
+                b"bonding_curve",
+                legacy_curve.creator.as_ref(),
+                legacy_curve.token_mint.as_ref(),
+                &[legacy_curve.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ZeroglazeError::InvalidBondingCurve)?;
+        require_keys_eq!(account.key(), expected, ZeroglazeError::InvalidBondingCurve);
+        let (creator_fee_wallet, _) = Pubkey::find_program_address(
+            &[
+                b"creator_fee_wallet",
+                legacy_curve.creator.as_ref(),
+                legacy_curve.token_mint.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            ctx.accounts.creator_fee_wallet.key(),
+            creator_fee_wallet,
+            ZeroglazeError::InvalidBondingCurve
+        );
+
+        let mut reserves = ctx.accounts.curve_reserves.load_init()?;
+        reserves.bonding_curve = account.key();
+        reserves.bump = ctx.bumps.curve_reserves;
+        let token_metadata = &mut ctx.accounts.token_metadata;
+        token_metadata.bonding_curve = account.key();
+        token_metadata.bump = ctx.bumps.token_metadata;
+        let stream_history = &mut ctx.accounts.stream_history;
+        stream_history.bonding_curve = account.key();
+        stream_history.bump = ctx.bumps.stream_history;
+        let bonding_curve = legacy_curve.split_into(&mut reserves, token_metadata, stream_history);
+
+        // Whatever the fee wallet holds is still owed to the creator
+        let creator_fee_balance = ctx.accounts.creator_fee_wallet.lamports();
+        reserves.creator_fees_withdrawn = reserves.creator_fees_collected
+            .saturating_sub(creator_fee_balance);
+
+        // Fund the SOL PDAs to rent exemption on top of what they owe, as
+        // create_token does at launch
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        for (wallet, owed) in [
+            (ctx.accounts.curve_sol_vault.to_account_info(), reserves.real_sol_reserves),
+            (ctx.accounts.creator_fee_wallet.to_account_info(), creator_fee_balance),
+            (ctx.accounts.holder_rewards_pool.to_account_info(), 0),
+        ] {
+            let top_up = rent_exempt_minimum
+                .saturating_add(owed)
+                .saturating_sub(wallet.lamports());
+            if top_up > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: wallet,
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+        }
+
+        // The account shrinks; its surplus rent stays with it until close_curve
+        realloc_with_rent(
+            &account,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + BondingCurve::INIT_SPACE,
+        )?;
+        let mut data = account.try_borrow_mut_data()?;
+        bonding_curve.try_serialize(&mut &mut data[..])?;
+
+        msg!("Bonding curve {} migrated to v{}", account.key(), BONDING_CURVE_VERSION);
+
+        Ok(())
+    }
+
+    /// Propose a new fee authority; takes effect once they call accept_creator
+    /// (Pubkey::default() cancels a pending transfer)
+    pub fn transfer_creator(
+        ctx: Context<TransferCreator>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let bonding_curve = &mut ctx.accounts.bonding_curve;
+        bonding_curve.pending_fee_authority = new_authority;
+
+        msg!("Fee authority transfer proposed to {}", new_authority);
+
+        Ok(())
+    }
+
+    /// Accept a pending fee authority transfer
+    pub fn accept_creator(
+        ctx: Context<AcceptCreator>,
+    ) -> Result<()> {
+        let bonding_curve = &mut ctx.accounts.bonding_curve;
+        let previous_authority = bonding_curve.fee_authority;
+        bonding_curve.fee_authority = ctx.accounts.new_authority.key();
+        bonding_curve.pending_fee_authority = Pubkey::default();
+
+        emit!(CreatorTransferred {
+            token_mint: bonding_curve.token_mint,
+            previous_authority,
+            new_authority: bonding_curve.fee_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Fee authority transferred to {}", bonding_curve.fee_authority);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Context Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializePlatform<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlatformState::INIT_SPACE,
+        seeds = [b"platform_state"],
+        bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateToken<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BondingCurve::INIT_SPACE,
+        seeds = [b"bonding_curve", creator.key().as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CurveReserves::LEN,
+        seeds = [b"curve_reserves", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + TokenMetadata::INIT_SPACE,
+        seeds = [b"token_metadata", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: Box<Account<'info, TokenMetadata>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + StreamHistory::INIT_SPACE,
+        seeds = [b"stream_history", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub stream_history: Account<'info, StreamHistory>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = 6,
+        mint::authority = token_mint,
+        mint::freeze_authority = token_mint,
+        seeds = [b"token_mint", creator.key().as_ref()],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_wallet", creator.key().as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_pool", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub holder_rewards_pool: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = platform_state.platform_fee_wallet @ ZeroglazeError::Unauthorized
+    )]
+    pub platform_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(mut, has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve)]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = token_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + BuyerRecord::INIT_SPACE,
+        seeds = [b"buyer_record", bonding_curve.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = platform_state.platform_fee_wallet @ ZeroglazeError::Unauthorized
+    )]
+    pub platform_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_wallet", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump
+    )]
+    pub creator_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_pool", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub holder_rewards_pool: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    /// Optional referrer paid a cut of the platform fee
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_stats", referrer_stats.referrer.as_ref()],
+        bump = referrer_stats.bump
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(mut, has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve)]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + BuyerRecord::INIT_SPACE,
+        seeds = [b"buyer_record", bonding_curve.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub seller_record: Account<'info, BuyerRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = platform_state.platform_fee_wallet @ ZeroglazeError::Unauthorized
+    )]
+    pub platform_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_wallet", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump
+    )]
+    pub creator_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_pool", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub holder_rewards_pool: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    /// Optional referrer paid a cut of the platform fee
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer_stats", referrer_stats.referrer.as_ref()],
+        bump = referrer_stats.bump
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
 
+#[derive(Accounts)]
+pub struct UpdateStreamStatus<'info> {
     #[account(
         mut,
-        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
-        bump
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump
     )]
-    /// CHECK: PDA for holding SOL reserves
-    pub curve_sol_vault: AccountInfo<'info>,
+    pub bonding_curve: Account<'info, BondingCurve>,
 
-    #[account(mut)]
-    pub platform_fee_wallet: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"stream_history", bonding_curve.key().as_ref()],
+        bump = stream_history.bump
+    )]
+    pub stream_history: Account<'info, StreamHistory>,
+
+    #[account(
+        constraint = authority.key() == platform_state.authority @ ZeroglazeError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+}
 
+#[derive(Accounts)]
+pub struct RelayStreamAttestation<'info> {
     #[account(
         mut,
-        seeds = [b"creator_fee_wallet", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
-        bump
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump
     )]
-    /// CHECK: PDA for collecting creator fees
-    pub creator_fee_wallet: AccountInfo<'info>,
+    pub bonding_curve: Account<'info, BondingCurve>,
 
     #[account(
         mut,
+        seeds = [b"stream_history", bonding_curve.key().as_ref()],
+        bump = stream_history.bump
+    )]
+    pub stream_history: Account<'info, StreamHistory>,
+
+    #[account(
+        seeds = [b"platform_state"],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    /// CHECK: address checked against the instructions sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchUpdateStreamStatus<'info> {
+    #[account(
+        constraint = authority.key() == platform_state.authority @ ZeroglazeError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
         seeds = [b"platform_state"],
         bump = platform_state.bump
     )]
     pub platform_state: Account<'info, PlatformState>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePlatformConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump = platform_state.bump,
+        constraint = authority.key() == platform_state.authority @ ZeroglazeError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerStats::INIT_SPACE,
+        seeds = [b"referrer_stats", referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
 
     #[account(mut)]
-    pub buyer: Signer<'info>,
+    pub referrer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SellTokens<'info> {
+pub struct ClaimHolderRewards<'info> {
     #[account(
-        mut,
         seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
         bump = bonding_curve.bump
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
+    #[account(mut, has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve)]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(
+        mut,
+        seeds = [b"buyer_record", bonding_curve.key().as_ref(), holder.key().as_ref()],
+        bump = holder_record.bump
+    )]
+    pub holder_record: Account<'info, BuyerRecord>,
+
+    #[account(
+        associated_token::mint = bonding_curve.token_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_pool", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub holder_rewards_pool: SystemAccount<'info>,
+
     #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
 
+#[derive(Accounts)]
+pub struct UpdateFeeRecipients<'info> {
     #[account(
         mut,
-        associated_token::mint = token_mint,
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump,
+        constraint = bonding_curve.fee_authority == fee_authority.key() @ ZeroglazeError::Unauthorized
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub fee_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawGraduatedLiquidity<'info> {
+    #[account(
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(mut, has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve)]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(
+        mut,
+        associated_token::mint = bonding_curve.token_mint,
         associated_token::authority = bonding_curve,
     )]
     pub curve_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = seller,
+        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        bump
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub curve_sol_vault: SystemAccount<'info>,
 
+    /// Holds the SOL side of the DEX pool until the pool is seeded
     #[account(
         mut,
-        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        seeds = [b"dex_liquidity", bonding_curve.token_mint.as_ref()],
         bump
     )]
-    /// CHECK: PDA for holding SOL reserves
-    pub curve_sol_vault: AccountInfo<'info>,
+    pub dex_liquidity: SystemAccount<'info>,
 
-    #[account(mut)]
-    pub platform_fee_wallet: SystemAccount<'info>,
+    /// Holds the tokens left on the curve until the pool is seeded
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = dex_liquidity,
+    )]
+    pub dex_liquidity_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = bonding_curve.token_mint @ ZeroglazeError::InvalidBondingCurve)]
+    pub token_mint: Account<'info, Mint>,
 
     #[account(
         mut,
-        seeds = [b"creator_fee_wallet", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
-        bump
+        address = platform_state.platform_fee_wallet @ ZeroglazeError::Unauthorized
     )]
-    /// CHECK: PDA for collecting creator fees
-    pub creator_fee_wallet: AccountInfo<'info>,
+    pub platform_fee_wallet: SystemAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"platform_state"],
-        bump = platform_state.bump
+        bump = platform_state.bump,
+        constraint = authority.key() == platform_state.authority @ ZeroglazeError::Unauthorized
     )]
     pub platform_state: Account<'info, PlatformState>,
 
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateStreamStatus<'info> {
+pub struct CloseCurve<'info> {
     #[account(
         mut,
         seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
-        bump = bonding_curve.bump
+        bump = bonding_curve.bump,
+        constraint = bonding_curve.fee_authority == fee_authority.key() @ ZeroglazeError::Unauthorized,
+        close = creator
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
     #[account(
-        constraint = authority.key() == platform_state.authority @ ZeroglazeError::Unauthorized
+        mut,
+        has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve,
+        close = creator
     )]
-    pub authority: Signer<'info>,
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(
+        mut,
+        has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve,
+        close = creator
+    )]
+    pub token_metadata: Box<Account<'info, TokenMetadata>>,
+
+    #[account(
+        mut,
+        seeds = [b"stream_history", bonding_curve.key().as_ref()],
+        bump = stream_history.bump,
+        close = creator
+    )]
+    pub stream_history: Account<'info, StreamHistory>,
+
+    #[account(
+        init,
+        payer = fee_authority,
+        space = 8 + GraduatedToken::INIT_SPACE,
+        seeds = [b"graduated_token", bonding_curve.token_mint.as_ref()],
+        bump
+    )]
+    pub graduated_token: Account<'info, GraduatedToken>,
+
+    #[account(
+        mut,
+        associated_token::mint = bonding_curve.token_mint,
+        associated_token::authority = bonding_curve,
+    )]
+    pub curve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_wallet", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump
+    )]
+    pub creator_fee_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_pool", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub holder_rewards_pool: SystemAccount<'info>,
+
+    /// Original creator, who paid the rent being returned
+    #[account(mut, address = bonding_curve.creator @ ZeroglazeError::Unauthorized)]
+    pub creator: SystemAccount<'info>,
+
+    /// Receives rounding dust left in the holder rewards pool
+    #[account(
+        mut,
+        address = platform_state.platform_fee_wallet @ ZeroglazeError::Unauthorized
+    )]
+    pub platform_fee_wallet: SystemAccount<'info>,
 
     #[account(
         seeds = [b"platform_state"],
         bump = platform_state.bump
     )]
     pub platform_state: Account<'info, PlatformState>,
+
+    #[account(mut)]
+    pub fee_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePlatform<'info> {
+    /// CHECK: may still hold a pre-versioning layout; checked and decoded in the handler
+    #[account(
+        mut,
+        seeds = [b"platform_state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub platform_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCurve<'info> {
+    /// CHECK: holds an older layout; checked and decoded in the handler
+    #[account(mut, owner = crate::ID)]
+    pub bonding_curve: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CurveReserves::LEN,
+        seeds = [b"curve_reserves", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TokenMetadata::INIT_SPACE,
+        seeds = [b"token_metadata", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: Box<Account<'info, TokenMetadata>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StreamHistory::INIT_SPACE,
+        seeds = [b"stream_history", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub stream_history: Box<Account<'info, StreamHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"curve_sol_vault", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub curve_sol_vault: SystemAccount<'info>,
+
+    /// CHECK: seeded by the decoded curve's creator and mint; checked in the handler
+    #[account(mut)]
+    pub creator_fee_wallet: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_pool", bonding_curve.key().as_ref()],
+        bump
+    )]
+    pub holder_rewards_pool: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferCreator<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump,
+        constraint = bonding_curve.fee_authority == fee_authority.key() @ ZeroglazeError::Unauthorized
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub fee_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptCreator<'info> {
+    #[account(
+        mut,
+        seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.bump,
+        constraint = bonding_curve.pending_fee_authority == new_authority.key() @ ZeroglazeError::Unauthorized
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub new_authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -623,20 +2283,26 @@ pub struct WithdrawCreatorFees<'info> {
     #[account(
         seeds = [b"bonding_curve", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
         bump = bonding_curve.bump,
-        constraint = bonding_curve.creator == creator.key() @ ZeroglazeError::Unauthorized
+        constraint = bonding_curve.fee_authority == fee_authority.key() @ ZeroglazeError::Unauthorized
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
+    #[account(mut, has_one = bonding_curve @ ZeroglazeError::InvalidBondingCurve)]
+    pub curve_reserves: AccountLoader<'info, CurveReserves>,
+
     #[account(
         mut,
         seeds = [b"creator_fee_wallet", bonding_curve.creator.as_ref(), bonding_curve.token_mint.as_ref()],
         bump
     )]
-    /// CHECK: PDA for collecting creator fees
-    pub creator_fee_wallet: AccountInfo<'info>,
+    pub creator_fee_wallet: SystemAccount<'info>,
 
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub fee_authority: Signer<'info>,
+
+    /// Receives the fee authority's share instead of its own wallet
+    #[account(mut)]
+    pub destination: Option<SystemAccount<'info>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -1,4 +1,11 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    ACCOUNT_RESERVED_BYTES, BPS_DENOMINATOR, CREATOR_FEE_OFFLINE_BPS,
+    HOLDER_REWARD_CLAIM_WINDOW_SECS, LIVE_FEE_TIER_COUNT, MAX_FEE_RECIPIENTS, REWARD_PRECISION,
+    STREAM_HISTORY_LEN, TOTAL_SUPPLY,
+};
+use crate::utils::calculate_fee;
+use crate::errors::ZeroglazeError;
 
 /// Global platform state
 #[account]
@@ -9,33 +16,168 @@ pub struct PlatformState {
     pub total_tokens_launched: u64,
     pub total_volume_sol: u64,
     pub total_fees_collected: u64,
+    pub max_stream_staleness_secs: i64,
+    pub stream_oracle: Pubkey, // Pubkey::default() = attestations disabled
+    pub live_fee_tiers_bps: [u16; LIVE_FEE_TIER_COUNT],
+    pub holder_reward_share_bps: u16, // share of the live creator fee paid to holders
+    pub referral_fee_bps: u16,        // share of the platform fee paid to referrers
+    pub total_referral_fees: u64,
     pub bump: u8,
+
+    // Layout
+    pub version: u8,
+    pub reserved: [u8; ACCOUNT_RESERVED_BYTES],
+}
+
+impl PlatformState {
+    /// Record a token launch
+    pub fn record_launch(&mut self) -> Result<()> {
+        self.total_tokens_launched = self.total_tokens_launched
+            .checked_add(1)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a trade's volume and the fees it paid
+    pub fn record_trade(&mut self, volume: u64, fees: u64, referral_fee: u64) -> Result<()> {
+        self.total_volume_sol = self.total_volume_sol
+            .checked_add(volume)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(fees)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.total_referral_fees = self.total_referral_fees
+            .checked_add(referral_fee)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
 }
 
-/// Bonding curve state for each token
+/// Per-token configuration, stream status and fee routing
+///
+/// Trades only read this account; everything a trade writes lives in
+/// `CurveReserves`, and the display strings live in `TokenMetadata`.
 #[account]
 #[derive(InitSpace)]
 pub struct BondingCurve {
-    // Token metadata
     pub creator: Pubkey,
     pub token_mint: Pubkey,
 
-    #[max_len(32)]
-    pub token_name: String,
+    // Wallet allowed to withdraw fees and manage the fee split; starts as the
+    // creator and moves via transfer_creator / accept_creator. PDA seeds keep
+    // using `creator`.
+    pub fee_authority: Pubkey,
+    pub pending_fee_authority: Pubkey,
 
-    #[max_len(10)]
-    pub token_symbol: String,
+    pub freeze_creator_allocation: bool,
 
-    #[max_len(200)]
-    pub token_uri: String,
+    // Stream status
+    pub is_live_streaming: bool,
+    pub last_stream_check: i64,
+    pub last_attestation_nonce: u64,
+    pub live_fee_tier: u8,
 
-    #[max_len(32)]
-    pub creator_twitter: String,
+    // Launch protection
+    pub launch_protection_slots: u64,
+    pub launch_max_buy_lamports: u64,
+    pub sniper_fee_bps: u16,
 
-    #[max_len(32)]
-    pub creator_twitch: String,
+    // Per-wallet holding cap (0 = uncapped)
+    pub max_holding_bps: u16,
 
-    pub freeze_creator_allocation: bool,
+    // Creator fee split (empty = everything to the creator)
+    #[max_len(MAX_FEE_RECIPIENTS)]
+    pub fee_recipients: Vec<FeeRecipient>,
+
+    // Metadata
+    pub created_at: i64,
+    pub created_slot: u64,
+    pub bump: u8,
+
+    // Layout
+    pub version: u8,
+    pub reserved: [u8; ACCOUNT_RESERVED_BYTES],
+}
+
+impl BondingCurve {
+    /// Whether the live fee applies, treating stale stream status as offline
+    pub fn is_live_at(&self, now: i64, max_staleness_secs: i64) -> bool {
+        self.is_live_streaming
+            && now.saturating_sub(self.last_stream_check) <= max_staleness_secs
+    }
+
+    /// Apply a stream status report made at `now` to the curve and its history
+    ///
+    /// Every status path (keeper update, batch, heartbeat, oracle attestation)
+    /// goes through here so sessions are tracked the same way.
+    pub fn set_stream_status(
+        &mut self,
+        stream_history: &mut StreamHistory,
+        is_live: bool,
+        now: i64,
+        max_staleness_secs: i64,
+    ) -> Result<()> {
+        stream_history.record_status(
+            self.is_live_streaming,
+            self.last_stream_check,
+            is_live,
+            now,
+            max_staleness_secs,
+        )?;
+
+        self.is_live_streaming = is_live;
+        self.last_stream_check = now;
+        Ok(())
+    }
+
+    /// Creator fee in basis points at `now`, using the platform's live fee tiers
+    pub fn creator_fee_bps_at(&self, now: i64, platform_state: &PlatformState) -> u16 {
+        if !self.is_live_at(now, platform_state.max_stream_staleness_secs) {
+            return CREATOR_FEE_OFFLINE_BPS;
+        }
+        platform_state
+            .live_fee_tiers_bps
+            .get(self.live_fee_tier as usize)
+            .copied()
+            .unwrap_or(CREATOR_FEE_OFFLINE_BPS)
+    }
+
+    /// Portion of a creator fee diverted to holders as the live bonus
+    pub fn holder_reward_for(
+        &self,
+        reserves: &CurveReserves,
+        creator_fee: u64,
+        now: i64,
+        platform_state: &PlatformState,
+    ) -> Result<u64> {
+        if reserves.reward_eligible_supply == 0
+            || !self.is_live_at(now, platform_state.max_stream_staleness_secs)
+        {
+            return Ok(0);
+        }
+        calculate_fee(creator_fee, platform_state.holder_reward_share_bps)
+    }
+
+    /// Maximum tokens a single wallet may hold, enforced only during the bonding phase
+    pub fn max_wallet_holding(&self, reserves: &CurveReserves) -> Option<u64> {
+        if reserves.is_graduated() || self.max_holding_bps == 0 {
+            return None;
+        }
+        Some((TOTAL_SUPPLY as u128 * self.max_holding_bps as u128 / BPS_DENOMINATOR as u128) as u64)
+    }
+
+    /// Slots elapsed since launch if still inside the protection window
+    pub fn launch_protection_elapsed(&self, slot: u64) -> Option<u64> {
+        let elapsed = slot.saturating_sub(self.created_slot);
+        (elapsed < self.launch_protection_slots).then_some(elapsed)
+    }
+}
+
+/// Curve reserves and trading stats, loaded zero-copy on every trade
+#[account(zero_copy)]
+pub struct CurveReserves {
+    // u128 as little-endian bytes: account data is only 8-byte aligned
+    pub reward_per_token_le: [u8; 16], // scaled by REWARD_PRECISION
 
     // Bonding curve parameters
     pub virtual_sol_reserves: u64,
@@ -47,20 +189,55 @@ pub struct BondingCurve {
     pub tokens_sold: u64,
     pub total_volume: u64,
     pub creator_fees_collected: u64,
+    pub creator_fees_withdrawn: u64,
 
-    // Stream status
-    pub is_live_streaming: bool,
-    pub last_stream_check: i64,
+    // Holder rewards (live bonus)
+    pub reward_eligible_supply: u64,
+    pub holder_rewards_distributed: u64,
+    pub holder_rewards_claimed: u64,
+    pub holder_rewards_forfeited: u64, // earned on tokens that left the wallet
 
-    // Graduation
-    pub graduated: bool,
-
-    // Metadata
-    pub created_at: i64,
+    pub graduated_at: i64,
+    pub bonding_curve: Pubkey,
+    pub graduated: u8, // bool
     pub bump: u8,
+    pub padding: [u8; 14],
 }
 
-impl BondingCurve {
+impl CurveReserves {
+    pub const LEN: usize = std::mem::size_of::<Self>();
+
+    pub fn is_graduated(&self) -> bool {
+        self.graduated != 0
+    }
+
+    pub fn graduate(&mut self, now: i64) {
+        self.graduated = 1;
+        self.graduated_at = now;
+    }
+
+    /// Whether holders may still claim rewards before the pool can be swept
+    pub fn holder_reward_claims_open(&self, now: i64) -> bool {
+        !self.is_graduated()
+            || now < self.graduated_at.saturating_add(HOLDER_REWARD_CLAIM_WINDOW_SECS)
+    }
+
+    /// Rewards distributed and not yet claimed or forfeited; rounding makes
+    /// this an upper bound on what holders can still claim
+    pub fn holder_rewards_outstanding(&self) -> u64 {
+        self.holder_rewards_distributed
+            .saturating_sub(self.holder_rewards_claimed)
+            .saturating_sub(self.holder_rewards_forfeited)
+    }
+
+    pub fn reward_per_token(&self) -> u128 {
+        u128::from_le_bytes(self.reward_per_token_le)
+    }
+
+    pub fn set_reward_per_token(&mut self, reward_per_token: u128) {
+        self.reward_per_token_le = reward_per_token.to_le_bytes();
+    }
+
     /// Calculate current price per token in lamports
     pub fn get_current_price(&self) -> Result<u64> {
         if self.virtual_token_reserves == 0 {
@@ -82,4 +259,339 @@ impl BondingCurve {
     pub fn get_progress(&self) -> u8 {
         ((self.tokens_sold as f64 / 800_000_000.0) * 100.0) as u8
     }
+
+    /// Spread `amount` lamports of rewards across the eligible supply
+    pub fn distribute_holder_rewards(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        require!(self.reward_eligible_supply > 0, ZeroglazeError::InvariantViolation);
+
+        let increment = (amount as u128)
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(ZeroglazeError::MathOverflow)?
+            / self.reward_eligible_supply as u128;
+        let reward_per_token = self.reward_per_token()
+            .checked_add(increment)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.set_reward_per_token(reward_per_token);
+        self.holder_rewards_distributed = self.holder_rewards_distributed
+            .checked_add(amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Credit a holder with rewards accrued since their last settlement
+    ///
+    /// Only tokens bought through the curve and still held earn rewards. The
+    /// program can't see when tokens left the wallet, so the stake shrinks to
+    /// `held_balance` before accruing and the whole period since the last
+    /// settlement is paid at the smaller stake. What the removed tokens
+    /// earned in that period is counted as forfeited.
+    pub fn settle_holder_rewards(&mut self, record: &mut BuyerRecord, held_balance: u64) -> Result<()> {
+        if held_balance < record.reward_balance {
+            let removed = record.reward_balance - held_balance;
+            let forfeited = self.earned_since(record, removed)?;
+            self.holder_rewards_forfeited = self.holder_rewards_forfeited
+                .checked_add(forfeited)
+                .ok_or(ZeroglazeError::MathOverflow)?;
+            self.reduce_reward_stake(record, removed);
+        }
+        self.accrue_holder_rewards(record)
+    }
+
+    /// Pay out a holder's unclaimed rewards
+    pub fn claim_holder_rewards(&mut self, record: &mut BuyerRecord) -> Result<u64> {
+        let amount = record.unclaimed_rewards;
+        require!(amount > 0, ZeroglazeError::NoRewardsToClaim);
+        record.unclaimed_rewards = 0;
+        self.holder_rewards_claimed = self.holder_rewards_claimed
+            .checked_add(amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(amount)
+    }
+
+    /// Add freshly bought tokens to a holder's reward stake
+    pub fn add_reward_stake(&mut self, record: &mut BuyerRecord, amount: u64) -> Result<()> {
+        self.accrue_holder_rewards(record)?;
+        record.reward_balance = record.reward_balance
+            .checked_add(amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.reward_eligible_supply = self.reward_eligible_supply
+            .checked_add(amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Remove up to `amount` tokens from a holder's reward stake
+    ///
+    /// The record must already be settled with `settle_holder_rewards`.
+    pub fn remove_reward_stake(&mut self, record: &mut BuyerRecord, amount: u64) -> Result<()> {
+        require!(
+            record.reward_per_token_paid == self.reward_per_token(),
+            ZeroglazeError::InvariantViolation
+        );
+        self.reduce_reward_stake(record, amount);
+        Ok(())
+    }
+
+    fn reduce_reward_stake(&mut self, record: &mut BuyerRecord, amount: u64) {
+        let amount = amount.min(record.reward_balance);
+        record.reward_balance -= amount;
+        self.reward_eligible_supply = self.reward_eligible_supply.saturating_sub(amount);
+    }
+
+    /// Move rewards earned by the current stake into `unclaimed_rewards`
+    fn accrue_holder_rewards(&self, record: &mut BuyerRecord) -> Result<()> {
+        let earned = self.earned_since(record, record.reward_balance)?;
+        record.unclaimed_rewards = record.unclaimed_rewards
+            .checked_add(earned)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        record.reward_per_token_paid = self.reward_per_token();
+        Ok(())
+    }
+
+    /// Rewards `stake` tokens earned since the record's last settlement
+    fn earned_since(&self, record: &BuyerRecord, stake: u64) -> Result<u64> {
+        let earned = (stake as u128)
+            .checked_mul(self.reward_per_token() - record.reward_per_token_paid)
+            .ok_or(ZeroglazeError::MathOverflow)?
+            / REWARD_PRECISION;
+        Ok(earned as u64)
+    }
+
+    /// Apply a buy to the curve reserves and trading stats
+    pub fn record_buy(
+        &mut self,
+        token_amount: u64,
+        sol_amount: u64,
+        total_cost: u64,
+        creator_fee: u64,
+    ) -> Result<()> {
+        self.virtual_sol_reserves = self.virtual_sol_reserves
+            .checked_add(sol_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.virtual_token_reserves = self.virtual_token_reserves
+            .checked_sub(token_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_add(sol_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.real_token_reserves = self.real_token_reserves
+            .checked_sub(token_amount)
+            .ok_or(ZeroglazeError::InsufficientCurveSupply)?;
+        self.tokens_sold = self.tokens_sold
+            .checked_add(token_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.total_volume = self.total_volume
+            .checked_add(total_cost)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.creator_fees_collected = self.creator_fees_collected
+            .checked_add(creator_fee)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Apply a sell to the curve reserves and trading stats
+    pub fn record_sell(
+        &mut self,
+        token_amount: u64,
+        sol_amount: u64,
+        creator_fee: u64,
+    ) -> Result<()> {
+        self.virtual_sol_reserves = self.virtual_sol_reserves
+            .checked_sub(sol_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.virtual_token_reserves = self.virtual_token_reserves
+            .checked_add(token_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.real_sol_reserves = self.real_sol_reserves
+            .checked_sub(sol_amount)
+            .ok_or(ZeroglazeError::InsufficientLiquidity)?;
+        self.real_token_reserves = self.real_token_reserves
+            .checked_add(token_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.tokens_sold = self.tokens_sold
+            .checked_sub(token_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.total_volume = self.total_volume
+            .checked_add(sol_amount)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.creator_fees_collected = self.creator_fees_collected
+            .checked_add(creator_fee)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Creator fees accrued but not yet withdrawn from the fee wallet
+    pub fn creator_fees_pending(&self) -> u64 {
+        self.creator_fees_collected.saturating_sub(self.creator_fees_withdrawn)
+    }
+}
+
+/// Display metadata for a token, kept off the trading path
+#[account]
+#[derive(InitSpace)]
+pub struct TokenMetadata {
+    pub bonding_curve: Pubkey,
+
+    #[max_len(32)]
+    pub token_name: String,
+
+    #[max_len(10)]
+    pub token_symbol: String,
+
+    #[max_len(200)]
+    pub token_uri: String,
+
+    #[max_len(32)]
+    pub creator_twitter: String,
+
+    #[max_len(32)]
+    pub creator_twitch: String,
+
+    pub bump: u8,
+}
+
+/// Per-wallet trade tracking for launch protection and holder rewards
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerRecord {
+    pub bonding_curve: Pubkey,
+    pub buyer: Pubkey,
+    pub launch_sol_bought: u64,
+
+    // Holder rewards
+    pub reward_balance: u64, // tokens bought through the curve and still held
+    pub reward_per_token_paid: u128,
+    pub unclaimed_rewards: u64,
+
+    pub bump: u8,
+}
+
+/// A collaborator's weighted share of the creator fees
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeRecipient {
+    pub wallet: Pubkey,
+    pub weight_bps: u16,
+}
+
+/// Lifetime stats for a registered referrer
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerStats {
+    pub referrer: Pubkey,
+    pub total_fees_earned: u64,
+    pub total_volume_referred: u64,
+    pub trade_count: u64,
+    pub bump: u8,
+}
+
+impl ReferrerStats {
+    /// Record a referred trade
+    pub fn record_trade(&mut self, volume: u64, referral_fee: u64) -> Result<()> {
+        self.total_volume_referred = self.total_volume_referred
+            .checked_add(volume)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.total_fees_earned = self.total_fees_earned
+            .checked_add(referral_fee)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        self.trade_count = self.trade_count
+            .checked_add(1)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Cumulative live-time accounting for a token's streamer
+#[account]
+#[derive(InitSpace)]
+pub struct StreamHistory {
+    pub bonding_curve: Pubkey,
+    pub total_live_secs: u64,
+    pub session_count: u32,
+    pub current_session_start: i64, // 0 when offline
+    pub sessions: [StreamSession; STREAM_HISTORY_LEN], // ring buffer of finished sessions
+    pub next_session_index: u8,
+    pub bump: u8,
+}
+
+/// A finished stream session
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamSession {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl StreamHistory {
+    /// Apply a stream status report made at `now`
+    ///
+    /// `was_live` and `last_check` are the curve's status before this report.
+    /// A session the keeper stopped confirming is closed at the moment it went
+    /// stale, matching when the live fee stopped applying.
+    pub fn record_status(
+        &mut self,
+        was_live: bool,
+        last_check: i64,
+        is_live: bool,
+        now: i64,
+        max_staleness_secs: i64,
+    ) -> Result<()> {
+        if was_live && self.current_session_start != 0 {
+            let stale_at = last_check.saturating_add(max_staleness_secs);
+            if !is_live || now > stale_at {
+                self.close_session(now.min(stale_at))?;
+            }
+        }
+
+        if is_live && self.current_session_start == 0 {
+            self.current_session_start = now;
+            self.session_count = self.session_count
+                .checked_add(1)
+                .ok_or(ZeroglazeError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    fn close_session(&mut self, end: i64) -> Result<()> {
+        let start = self.current_session_start;
+        let end = end.max(start);
+        self.total_live_secs = self.total_live_secs
+            .checked_add((end - start) as u64)
+            .ok_or(ZeroglazeError::MathOverflow)?;
+
+        self.sessions[self.next_session_index as usize] = StreamSession { start, end };
+        self.next_session_index = ((self.next_session_index as usize + 1) % STREAM_HISTORY_LEN) as u8;
+        self.current_session_start = 0;
+        Ok(())
+    }
+}
+
+/// Compact history left behind when a graduated curve is closed
+#[account]
+#[derive(InitSpace)]
+pub struct GraduatedToken {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+
+    #[max_len(10)]
+    pub token_symbol: String,
+
+    pub tokens_sold: u64,
+    pub total_volume: u64,
+    pub creator_fees_collected: u64,
+    pub holder_rewards_distributed: u64,
+    pub total_live_secs: u64,
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+/// Stream status observed by the off-chain oracle, signed with its ed25519 key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StreamAttestation {
+    pub token_mint: Pubkey,
+    pub is_live: bool,
+    pub timestamp: i64,
+    pub nonce: u64,
 }
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use crate::constants::CURVE_SUPPLY;
+use crate::errors::ZeroglazeError;
+use crate::state::{BondingCurve, CurveReserves, PlatformState};
+use crate::utils::{
+    calculate_buy_cost, calculate_fee, calculate_sell_output, calculate_sniper_fee_bps,
+};
+
+// Trade pricing shared by buy_tokens / sell_tokens, the simulator and client
+// quotes. Nothing here writes state: callers settle the trader's reward stake
+// first and apply the result themselves.
+
+/// What a buy costs, split the way `buy_tokens` moves the lamports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuyQuote {
+    pub token_amount: u64,
+    pub sol_required: u64, // into the curve
+    pub platform_fee: u64, // including the referral fee
+    pub referral_fee: u64, // paid to the referrer out of the platform fee
+    pub creator_fee: u64,  // to the creator fee wallet, including the sniper fee
+    pub holder_reward: u64,
+    pub sniper_fee: u64,
+    pub total_cost: u64,        // what the buyer pays; pass as max_sol_cost
+    pub launch_sol_bought: u64, // buyer's launch window total after this buy
+}
+
+/// What a sell returns, split the way `sell_tokens` moves the lamports
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SellQuote {
+    pub token_amount: u64,
+    pub sol_to_return: u64, // out of the curve
+    pub platform_fee: u64,  // including the referral fee
+    pub referral_fee: u64,  // paid to the referrer out of the platform fee
+    pub creator_fee: u64,   // to the creator fee wallet
+    pub holder_reward: u64,
+    pub net_output: u64, // what the seller receives; pass as min_sol_output
+}
+
+/// Price buying `token_amount` tokens at `now` / `slot`
+///
+/// `launch_sol_bought` is the buyer's `BuyerRecord::launch_sol_bought`; it
+/// only matters during launch protection. `reserves` must already reflect
+/// the buyer's settled reward stake.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_buy(
+    curve: &BondingCurve,
+    reserves: &CurveReserves,
+    platform: &PlatformState,
+    token_amount: u64,
+    now: i64,
+    slot: u64,
+    launch_sol_bought: u64,
+    referred: bool,
+) -> Result<BuyQuote> {
+    require!(!reserves.is_graduated(), ZeroglazeError::TokenGraduated);
+    require!(token_amount > 0, ZeroglazeError::InvalidAmount);
+    let tokens_sold = reserves.tokens_sold
+        .checked_add(token_amount)
+        .ok_or(ZeroglazeError::MathOverflow)?;
+    require!(tokens_sold <= CURVE_SUPPLY, ZeroglazeError::InsufficientCurveSupply);
+
+    let (sol_required, platform_fee, creator_fee, _) = calculate_buy_cost(
+        reserves.virtual_sol_reserves,
+        reserves.virtual_token_reserves,
+        token_amount,
+        curve.creator_fee_bps_at(now, platform),
+    )?;
+
+    // Launch protection: per-wallet buy cap and decaying sniper fee
+    let (sniper_fee, launch_sol_bought) = match curve.launch_protection_elapsed(slot) {
+        Some(slots_elapsed) => {
+            let launch_sol_bought = launch_sol_bought
+                .checked_add(sol_required)
+                .ok_or(ZeroglazeError::MathOverflow)?;
+            require!(
+                curve.launch_max_buy_lamports == 0
+                    || launch_sol_bought <= curve.launch_max_buy_lamports,
+                ZeroglazeError::LaunchBuyLimitExceeded
+            );
+
+            let sniper_fee_bps = calculate_sniper_fee_bps(
+                curve.sniper_fee_bps,
+                curve.launch_protection_slots,
+                slots_elapsed,
+            );
+            (calculate_fee(sol_required, sniper_fee_bps)?, launch_sol_bought)
+        }
+        None => (0, launch_sol_bought),
+    };
+
+    // Live bonus: existing holders get a share of the live creator fee
+    let holder_reward = curve.holder_reward_for(reserves, creator_fee, now, platform)?;
+
+    // Sniper fees accrue to the creator alongside the regular creator fee
+    let creator_fee = (creator_fee - holder_reward)
+        .checked_add(sniper_fee)
+        .ok_or(ZeroglazeError::MathOverflow)?;
+
+    let total_cost = sol_required
+        .checked_add(platform_fee)
+        .ok_or(ZeroglazeError::MathOverflow)?
+        .checked_add(creator_fee)
+        .ok_or(ZeroglazeError::MathOverflow)?
+        .checked_add(holder_reward)
+        .ok_or(ZeroglazeError::MathOverflow)?;
+
+    Ok(BuyQuote {
+        token_amount,
+        sol_required,
+        platform_fee,
+        referral_fee: referral_fee(platform_fee, platform, referred)?,
+        creator_fee,
+        holder_reward,
+        sniper_fee,
+        total_cost,
+        launch_sol_bought,
+    })
+}
+
+/// Price selling `token_amount` tokens at `now`
+///
+/// `reserves` must already have the sold tokens removed from the seller's
+/// reward stake, so the live bonus goes to the holders that remain.
+pub fn compute_sell(
+    curve: &BondingCurve,
+    reserves: &CurveReserves,
+    platform: &PlatformState,
+    token_amount: u64,
+    now: i64,
+    referred: bool,
+) -> Result<SellQuote> {
+    require!(!reserves.is_graduated(), ZeroglazeError::TokenGraduated);
+    require!(token_amount > 0, ZeroglazeError::InvalidAmount);
+
+    let (sol_to_return, platform_fee, creator_fee, net_output) = calculate_sell_output(
+        reserves.virtual_sol_reserves,
+        reserves.virtual_token_reserves,
+        token_amount,
+        curve.creator_fee_bps_at(now, platform),
+    )?;
+    require!(
+        reserves.real_sol_reserves >= sol_to_return,
+        ZeroglazeError::InsufficientLiquidity
+    );
+
+    let holder_reward = curve.holder_reward_for(reserves, creator_fee, now, platform)?;
+
+    Ok(SellQuote {
+        token_amount,
+        sol_to_return,
+        platform_fee,
+        referral_fee: referral_fee(platform_fee, platform, referred)?,
+        creator_fee: creator_fee - holder_reward,
+        holder_reward,
+        net_output,
+    })
+}
+
+/// Referrer's cut of the platform fee
+fn referral_fee(platform_fee: u64, platform: &PlatformState, referred: bool) -> Result<u64> {
+    if !referred {
+        return Ok(0);
+    }
+    calculate_fee(platform_fee, platform.referral_fee_bps)
+}
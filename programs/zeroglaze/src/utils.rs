@@ -1,6 +1,34 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
 use crate::errors::ZeroglazeError;
-use crate::constants::BPS_DENOMINATOR;
+use crate::constants::{BPS_DENOMINATOR, MAX_FEE_RECIPIENTS};
+use crate::state::FeeRecipient;
+
+/// Resize a program-owned account to `new_len`, topping up rent from `payer`
+pub fn realloc_with_rent<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
 
 /// Calculate fee amount based on basis points
 pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
@@ -13,12 +41,18 @@ pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
     Ok(fee as u64)
 }
 
+/// `k / reserves` rounded up, as a lamport amount
+fn div_ceil(k: u128, reserves: u64) -> Result<u64> {
+    require!(reserves > 0, ZeroglazeError::InsufficientLiquidity);
+    u64::try_from(k.div_ceil(reserves as u128)).map_err(|_| error!(ZeroglazeError::MathOverflow))
+}
+
 /// Calculate buy cost with fees
 pub fn calculate_buy_cost(
     virtual_sol_reserves: u64,
     virtual_token_reserves: u64,
     token_amount: u64,
-    is_live: bool,
+    creator_fee_bps: u16,
 ) -> Result<(u64, u64, u64, u64)> {
     use crate::constants::*;
 
@@ -32,7 +66,8 @@ pub fn calculate_buy_cost(
         .checked_sub(token_amount)
         .ok_or(ZeroglazeError::MathOverflow)?;
 
-    let new_virtual_sol_reserves = (k / new_virtual_token_reserves as u128) as u64;
+    // Round up so rounding never shrinks k in the buyer's favor
+    let new_virtual_sol_reserves = div_ceil(k, new_virtual_token_reserves)?;
 
     let sol_required = new_virtual_sol_reserves
         .checked_sub(virtual_sol_reserves)
@@ -40,11 +75,7 @@ pub fn calculate_buy_cost(
 
     // Calculate fees
     let platform_fee = calculate_fee(sol_required, PLATFORM_FEE_BPS)?;
-    let creator_fee = if is_live {
-        calculate_fee(sol_required, CREATOR_FEE_LIVE_BPS)?
-    } else {
-        calculate_fee(sol_required, CREATOR_FEE_OFFLINE_BPS)?
-    };
+    let creator_fee = calculate_fee(sol_required, creator_fee_bps)?;
 
     let total_cost = sol_required
         .checked_add(platform_fee)
@@ -55,12 +86,41 @@ pub fn calculate_buy_cost(
     Ok((sol_required, platform_fee, creator_fee, total_cost))
 }
 
+/// Calculate tokens received for a SOL amount spent on the curve (before fees)
+///
+/// Rounds in the curve's favor so that quoting the result with
+/// `calculate_buy_cost` never costs more than `sol_amount`.
+pub fn calculate_tokens_for_sol(
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    sol_amount: u64,
+) -> Result<u64> {
+    let k: u128 = (virtual_sol_reserves as u128)
+        .checked_mul(virtual_token_reserves as u128)
+        .ok_or(ZeroglazeError::MathOverflow)?;
+
+    let new_virtual_sol_reserves = (virtual_sol_reserves as u128)
+        .checked_add(sol_amount as u128)
+        .ok_or(ZeroglazeError::MathOverflow)?;
+
+    let new_virtual_token_reserves = k
+        .checked_add(
+            new_virtual_sol_reserves
+                .checked_sub(1)
+                .ok_or(ZeroglazeError::MathOverflow)?,
+        )
+        .ok_or(ZeroglazeError::MathOverflow)?
+        / new_virtual_sol_reserves;
+
+    Ok((virtual_token_reserves as u128 - new_virtual_token_reserves) as u64)
+}
+
 /// Calculate sell output with fees
 pub fn calculate_sell_output(
     virtual_sol_reserves: u64,
     virtual_token_reserves: u64,
     token_amount: u64,
-    is_live: bool,
+    creator_fee_bps: u16,
 ) -> Result<(u64, u64, u64, u64)> {
     use crate::constants::*;
 
@@ -74,7 +134,8 @@ pub fn calculate_sell_output(
         .checked_add(token_amount)
         .ok_or(ZeroglazeError::MathOverflow)?;
 
-    let new_virtual_sol_reserves = (k / new_virtual_token_reserves as u128) as u64;
+    // Round up so rounding never shrinks k in the seller's favor
+    let new_virtual_sol_reserves = div_ceil(k, new_virtual_token_reserves)?;
 
     let sol_to_return = virtual_sol_reserves
         .checked_sub(new_virtual_sol_reserves)
@@ -82,11 +143,7 @@ pub fn calculate_sell_output(
 
     // Calculate fees
     let platform_fee = calculate_fee(sol_to_return, PLATFORM_FEE_BPS)?;
-    let creator_fee = if is_live {
-        calculate_fee(sol_to_return, CREATOR_FEE_LIVE_BPS)?
-    } else {
-        calculate_fee(sol_to_return, CREATOR_FEE_OFFLINE_BPS)?
-    };
+    let creator_fee = calculate_fee(sol_to_return, creator_fee_bps)?;
 
     let net_output = sol_to_return
         .checked_sub(platform_fee)
@@ -97,6 +154,170 @@ pub fn calculate_sell_output(
     Ok((sol_to_return, platform_fee, creator_fee, net_output))
 }
 
+/// Whether a trade pays a referral
+///
+/// `referrer` and `stats_referrer` are the referrer wallet and the wallet
+/// recorded in the passed `ReferrerStats`; both or neither must be present.
+pub fn validate_referrer(
+    referrer: Option<Pubkey>,
+    stats_referrer: Option<Pubkey>,
+    trader: Pubkey,
+) -> Result<bool> {
+    match (referrer, stats_referrer) {
+        (None, None) => Ok(false),
+        (Some(referrer), Some(stats_referrer)) => {
+            require!(
+                referrer == stats_referrer && referrer != trader,
+                ZeroglazeError::InvalidReferrer
+            );
+            Ok(true)
+        }
+        _ => err!(ZeroglazeError::InvalidReferrer),
+    }
+}
+
+/// Check a creator fee split: unique wallets with non-zero weights summing
+/// to 100%, or empty to pay everything to the creator
+pub fn validate_fee_recipients(recipients: &[FeeRecipient]) -> Result<()> {
+    if recipients.is_empty() {
+        return Ok(());
+    }
+    require!(
+        recipients.len() <= MAX_FEE_RECIPIENTS,
+        ZeroglazeError::InvalidFeeRecipients
+    );
+
+    let mut total_bps = 0u64;
+    for (i, recipient) in recipients.iter().enumerate() {
+        require!(
+            recipient.weight_bps > 0
+                && recipients[..i].iter().all(|other| other.wallet != recipient.wallet),
+            ZeroglazeError::InvalidFeeRecipients
+        );
+        total_bps += recipient.weight_bps as u64;
+    }
+    require!(total_bps == BPS_DENOMINATOR, ZeroglazeError::InvalidFeeRecipients);
+
+    Ok(())
+}
+
+/// Split `amount` by recipient weight; rounding dust goes to the first recipient
+pub fn split_by_weight(amount: u64, recipients: &[FeeRecipient]) -> Vec<u64> {
+    let mut shares: Vec<u64> = recipients
+        .iter()
+        .map(|recipient| {
+            (amount as u128 * recipient.weight_bps as u128 / BPS_DENOMINATOR as u128) as u64
+        })
+        .collect();
+    let dust = amount - shares.iter().sum::<u64>();
+    if let Some(first) = shares.first_mut() {
+        *first += dust;
+    }
+    shares
+}
+
+/// Sniper fee in basis points, decaying linearly to zero over the launch window
+pub fn calculate_sniper_fee_bps(
+    max_fee_bps: u16,
+    protection_slots: u64,
+    slots_elapsed: u64,
+) -> u16 {
+    if slots_elapsed >= protection_slots {
+        return 0;
+    }
+    let remaining = protection_slots - slots_elapsed;
+    ((max_fee_bps as u128 * remaining as u128) / protection_slots as u128) as u16
+}
+
+/// Verify curve bookkeeping against the balances actually held on-chain
+///
+/// Anyone can transfer lamports or tokens into the vault and curve token
+/// account, so a surplus is tolerated; holding less than the recorded
+/// reserves is not.
+pub fn check_reserve_invariants(
+    vault_lamports: u64,
+    vault_rent_exempt_minimum: u64,
+    real_sol_reserves: u64,
+    curve_token_balance: u64,
+    real_token_reserves: u64,
+) -> Result<()> {
+    let vault_sol_balance = vault_lamports
+        .checked_sub(vault_rent_exempt_minimum)
+        .ok_or(ZeroglazeError::InvariantViolation)?;
+
+    require!(
+        vault_sol_balance >= real_sol_reserves,
+        ZeroglazeError::InvariantViolation
+    );
+    require!(
+        curve_token_balance >= real_token_reserves,
+        ZeroglazeError::InvariantViolation
+    );
+
+    Ok(())
+}
+
+/// Check that `ix` is an Ed25519 program instruction verifying exactly one
+/// signature by `signer` over `message`, with all data inline
+///
+/// The Ed25519 program has already rejected the transaction if the signature
+/// itself is invalid; this only ensures it covered the key and bytes we expect.
+pub fn verify_ed25519_instruction(
+    ix: &Instruction,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const PUBKEY_LEN: usize = 32;
+    const SIGNATURE_LEN: usize = 64;
+
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ZeroglazeError::InvalidAttestation);
+    require!(ix.accounts.is_empty(), ZeroglazeError::InvalidAttestation);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        ZeroglazeError::InvalidAttestation
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix_index = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // Data referenced from other instructions could be swapped out by the relayer
+    require!(
+        signature_ix_index == u16::MAX
+            && pubkey_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        ZeroglazeError::InvalidAttestation
+    );
+    require!(
+        data.len() >= signature_offset + SIGNATURE_LEN,
+        ZeroglazeError::InvalidAttestation
+    );
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(ZeroglazeError::InvalidAttestation)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ZeroglazeError::InvalidAttestation)?;
+
+    require!(
+        signed_pubkey == signer.as_ref() && signed_message == message,
+        ZeroglazeError::InvalidAttestation
+    );
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,11 +336,157 @@ mod tests {
     #[test]
     fn test_buy_cost_calculation() {
         let (sol_required, platform_fee, creator_fee, total) =
-            calculate_buy_cost(30_000_000_000, 1_073_000_000, 1_000_000, true).unwrap();
+            calculate_buy_cost(30_000_000_000, 1_073_000_000, 1_000_000, 200).unwrap();
 
         assert!(sol_required > 0);
         assert_eq!(platform_fee, sol_required / 100); // 1%
         assert_eq!(creator_fee, sol_required * 2 / 100); // 2%
         assert_eq!(total, sol_required + platform_fee + creator_fee);
     }
+
+    #[test]
+    fn test_tokens_for_sol_stays_within_budget() {
+        for sol_amount in [1, 1_000, 27_985_074, 1_000_000_000, 50_000_000_000] {
+            let tokens = calculate_tokens_for_sol(30_000_000_000, 1_073_000_000, sol_amount).unwrap();
+            if tokens == 0 {
+                continue;
+            }
+            let (sol_required, _, _, _) =
+                calculate_buy_cost(30_000_000_000, 1_073_000_000, tokens, 20).unwrap();
+            assert!(sol_required <= sol_amount);
+
+            // One more token costs at least the whole budget
+            let (sol_required, _, _, _) =
+                calculate_buy_cost(30_000_000_000, 1_073_000_000, tokens + 1, 20).unwrap();
+            assert!(sol_required >= sol_amount);
+        }
+    }
+
+    #[test]
+    fn test_sniper_fee_decay() {
+        // Full fee in the launch slot, decaying linearly
+        assert_eq!(calculate_sniper_fee_bps(5_000, 100, 0), 5_000);
+        assert_eq!(calculate_sniper_fee_bps(5_000, 100, 50), 2_500);
+        assert_eq!(calculate_sniper_fee_bps(5_000, 100, 99), 50);
+
+        // Nothing once the window has passed or when disabled
+        assert_eq!(calculate_sniper_fee_bps(5_000, 100, 100), 0);
+        assert_eq!(calculate_sniper_fee_bps(5_000, 0, 0), 0);
+    }
+
+    #[test]
+    fn test_reserve_invariants() {
+        let rent = 890_880;
+
+        // Balances match bookkeeping exactly
+        assert!(check_reserve_invariants(rent + 5_000, rent, 5_000, 700, 700).is_ok());
+
+        // Unsolicited deposits are tolerated
+        assert!(check_reserve_invariants(rent + 6_000, rent, 5_000, 800, 700).is_ok());
+
+        // Vault holds less SOL than recorded
+        assert!(check_reserve_invariants(rent + 4_999, rent, 5_000, 700, 700).is_err());
+
+        // Vault dipped into its rent-exempt reserve
+        assert!(check_reserve_invariants(rent - 1, rent, 0, 700, 700).is_err());
+
+        // Curve token account holds fewer tokens than recorded
+        assert!(check_reserve_invariants(rent + 5_000, rent, 5_000, 699, 700).is_err());
+    }
+
+    #[test]
+    fn test_validate_referrer() {
+        let referrer = Pubkey::new_unique();
+        let trader = Pubkey::new_unique();
+
+        assert!(validate_referrer(Some(referrer), Some(referrer), trader).unwrap());
+        assert!(!validate_referrer(None, None, trader).unwrap());
+
+        // Stats for someone else, a missing half, or self-referral
+        assert!(validate_referrer(Some(referrer), Some(trader), trader).is_err());
+        assert!(validate_referrer(Some(referrer), None, trader).is_err());
+        assert!(validate_referrer(None, Some(referrer), trader).is_err());
+        assert!(validate_referrer(Some(trader), Some(trader), trader).is_err());
+    }
+
+    fn recipient(weight_bps: u16) -> FeeRecipient {
+        FeeRecipient { wallet: Pubkey::new_unique(), weight_bps }
+    }
+
+    #[test]
+    fn test_validate_fee_recipients() {
+        assert!(validate_fee_recipients(&[]).is_ok());
+        assert!(validate_fee_recipients(&[recipient(10_000)]).is_ok());
+        assert!(validate_fee_recipients(&[recipient(6_000), recipient(4_000)]).is_ok());
+
+        // Weights must sum to exactly 100%
+        assert!(validate_fee_recipients(&[recipient(6_000), recipient(3_999)]).is_err());
+        assert!(validate_fee_recipients(&[recipient(10_000), recipient(0)]).is_err());
+
+        // No duplicates or oversized splits
+        let duplicate = recipient(5_000);
+        assert!(validate_fee_recipients(&[duplicate, duplicate]).is_err());
+        let too_many: Vec<_> = (0..5).map(|_| recipient(2_000)).collect();
+        assert!(validate_fee_recipients(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_split_by_weight() {
+        let recipients = [recipient(5_000), recipient(3_000), recipient(2_000)];
+        assert_eq!(split_by_weight(1_000, &recipients), vec![500, 300, 200]);
+
+        // Dust goes to the first recipient and nothing is lost
+        let shares = split_by_weight(1_001, &recipients);
+        assert_eq!(shares, vec![501, 300, 200]);
+        let shares = split_by_weight(7, &[recipient(3_333), recipient(3_333), recipient(3_334)]);
+        assert_eq!(shares.iter().sum::<u64>(), 7);
+    }
+
+    fn ed25519_ix(signer: &Pubkey, message: &[u8], ix_index: u16) -> Instruction {
+        let pubkey_offset: u16 = 16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            ix_index,
+            pubkey_offset,
+            ix_index,
+            message_offset,
+            message.len() as u16,
+            ix_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+
+        Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+    }
+
+    #[test]
+    fn test_verify_ed25519_instruction() {
+        let oracle = Pubkey::new_unique();
+        let message = b"attestation";
+
+        assert!(verify_ed25519_instruction(&ed25519_ix(&oracle, message, u16::MAX), &oracle, message).is_ok());
+
+        // Different key or message
+        let other = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&ed25519_ix(&other, message, u16::MAX), &oracle, message).is_err());
+        assert!(verify_ed25519_instruction(&ed25519_ix(&oracle, b"attestatioN", u16::MAX), &oracle, message).is_err());
+
+        // Signed data pulled from another instruction
+        assert!(verify_ed25519_instruction(&ed25519_ix(&oracle, message, 0), &oracle, message).is_err());
+
+        // Not the Ed25519 program, or truncated data
+        let mut ix = ed25519_ix(&oracle, message, u16::MAX);
+        ix.program_id = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_err());
+        let mut ix = ed25519_ix(&oracle, message, u16::MAX);
+        ix.data.truncate(100);
+        assert!(verify_ed25519_instruction(&ix, &oracle, message).is_err());
+    }
 }
@@ -0,0 +1,140 @@
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::AccountMeta;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::TransactionError;
+use solana_program_test::BanksClientError;
+use zeroglaze::constants::DEFAULT_MAX_STREAM_STALENESS_SECS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze_test_harness::*;
+
+async fn setup(count: usize) -> (TestEnv, Vec<TokenAccounts>) {
+    let mut env = TestEnv::new().await;
+    let mut tokens = Vec::with_capacity(count);
+    for _ in 0..count {
+        let creator = env.funded_keypair(10_000_000_000).await;
+        tokens.push(env.create_token(&creator, false).await.unwrap());
+    }
+    (env, tokens)
+}
+
+#[tokio::test]
+async fn batch_updates_every_curve() {
+    let (mut env, tokens) = setup(3).await;
+    let authority = env.payer();
+    let refs: Vec<_> = tokens.iter().collect();
+
+    env.batch_update_stream_status(&refs, &authority, vec![true, false, true])
+        .await
+        .unwrap();
+
+    assert!(env.bonding_curve(&tokens[0]).await.is_live_streaming);
+    assert!(!env.bonding_curve(&tokens[1]).await.is_live_streaming);
+    assert!(env.bonding_curve(&tokens[2]).await.is_live_streaming);
+}
+
+#[tokio::test]
+async fn batch_skips_unchanged_fresh_curves() {
+    let (mut env, tokens) = setup(2).await;
+    let authority = env.payer();
+    let refs: Vec<_> = tokens.iter().collect();
+    env.advance_clock(10).await;
+
+    let before = env.bonding_curve(&tokens[1]).await.last_stream_check;
+    env.batch_update_stream_status(&refs, &authority, vec![true, false])
+        .await
+        .unwrap();
+
+    let changed = env.bonding_curve(&tokens[0]).await;
+    assert!(changed.is_live_streaming);
+    assert_eq!(changed.last_stream_check, before + 10);
+    assert_eq!(env.bonding_curve(&tokens[1]).await.last_stream_check, before);
+}
+
+#[tokio::test]
+async fn batch_refreshes_unchanged_curves_before_they_go_stale() {
+    let (mut env, tokens) = setup(1).await;
+    let authority = env.payer();
+    let before = env.bonding_curve(&tokens[0]).await.last_stream_check;
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS / 2).await;
+
+    env.batch_update_stream_status(&[&tokens[0]], &authority, vec![false])
+        .await
+        .unwrap();
+
+    let curve = env.bonding_curve(&tokens[0]).await;
+    assert!(!curve.is_live_streaming);
+    assert_eq!(curve.last_stream_check, before + DEFAULT_MAX_STREAM_STALENESS_SECS / 2);
+}
+
+#[tokio::test]
+async fn batch_rejects_length_mismatch() {
+    let (mut env, tokens) = setup(2).await;
+    let authority = env.payer();
+    let refs: Vec<_> = tokens.iter().collect();
+
+    let result = env.batch_update_stream_status(&refs, &authority, vec![true]).await;
+    assert_program_error(result, ZeroglazeError::StreamBatchLengthMismatch);
+}
+
+#[tokio::test]
+async fn batch_rejects_non_curve_accounts() {
+    let (mut env, _) = setup(1).await;
+    let authority = env.payer();
+
+    let mut accounts = zeroglaze::accounts::BatchUpdateStreamStatus {
+        authority: authority.pubkey(),
+        platform_state: pda::platform_state(),
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(pda::platform_state(), false));
+    accounts.push(AccountMeta::new(pda::platform_state(), false));
+    let ix = Instruction {
+        program_id: zeroglaze::ID,
+        accounts,
+        data: zeroglaze::instruction::BatchUpdateStreamStatus { statuses: vec![true] }.data(),
+    };
+
+    let result = env.process(&[ix], &[]).await;
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, ErrorCode::AccountDiscriminatorMismatch as u32),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn batch_rejects_mismatched_stream_history() {
+    let (mut env, tokens) = setup(2).await;
+    let authority = env.payer();
+
+    let mut accounts = zeroglaze::accounts::BatchUpdateStreamStatus {
+        authority: authority.pubkey(),
+        platform_state: pda::platform_state(),
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(tokens[0].bonding_curve, false));
+    accounts.push(AccountMeta::new(tokens[1].stream_history, false));
+    let ix = Instruction {
+        program_id: zeroglaze::ID,
+        accounts,
+        data: zeroglaze::instruction::BatchUpdateStreamStatus { statuses: vec![true] }.data(),
+    };
+
+    let result = env.process(&[ix], &[]).await;
+    assert_program_error(result, ZeroglazeError::InvalidBondingCurve);
+}
+
+#[tokio::test]
+async fn batch_requires_platform_authority() {
+    let (mut env, tokens) = setup(1).await;
+    let intruder = env.funded_keypair(1_000_000_000).await;
+
+    let result = env
+        .batch_update_stream_status(&[&tokens[0]], &intruder, vec![true])
+        .await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
@@ -0,0 +1,84 @@
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::Space;
+use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
+use solana_sdk::signature::Signer;
+use zeroglaze::constants::CREATOR_FEE_OFFLINE_BPS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::state::BuyerRecord;
+use zeroglaze::utils::calculate_buy_cost;
+use zeroglaze_test_harness::*;
+
+#[tokio::test]
+async fn buy_moves_lamports_to_every_party_exactly() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    let curve = env.curve_reserves(&token).await;
+    let buy_amount = 50_000_000;
+    let (sol_required, platform_fee, creator_fee, total_cost) = calculate_buy_cost(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        buy_amount,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap();
+
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let buyer_before = env.lamports(&buyer.pubkey()).await;
+    let vault_before = env.lamports(&token.curve_sol_vault).await;
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    let creator_fee_before = env.lamports(&token.creator_fee_wallet).await;
+    // The first buy also opens the buyer's token account and buyer record
+    let account_rent = env.rent_exempt_minimum(SplTokenAccount::LEN).await
+        + env.rent_exempt_minimum(8 + BuyerRecord::INIT_SPACE).await;
+
+    env.buy(&token, &buyer, buy_amount, total_cost).await.unwrap();
+
+    assert_eq!(env.lamports(&buyer.pubkey()).await, buyer_before - total_cost - account_rent);
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, vault_before + sol_required);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + platform_fee);
+    assert_eq!(
+        env.lamports(&token.creator_fee_wallet).await,
+        creator_fee_before + creator_fee
+    );
+    assert_eq!(total_cost, sol_required + platform_fee + creator_fee);
+
+    let buyer_token_account =
+        anchor_spl::associated_token::get_associated_token_address(&buyer.pubkey(), &token.token_mint);
+    assert_eq!(env.token_balance(&buyer_token_account).await, buy_amount);
+
+    let after = env.curve_reserves(&token).await;
+    assert_eq!(after.real_sol_reserves, curve.real_sol_reserves + sol_required);
+    assert_eq!(after.real_token_reserves, curve.real_token_reserves - buy_amount);
+    assert_eq!(after.tokens_sold, buy_amount);
+    assert_eq!(after.total_volume, total_cost);
+    assert_eq!(after.creator_fees_collected, creator_fee);
+
+    let platform = env.platform_state().await;
+    assert_eq!(platform.total_volume_sol, total_cost);
+    assert_eq!(platform.total_fees_collected, platform_fee + creator_fee);
+}
+
+#[tokio::test]
+async fn buy_respects_max_sol_cost() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    let curve = env.curve_reserves(&token).await;
+    let total_cost = calculate_buy_cost(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        1_000_000,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap()
+    .3;
+
+    let result = env.buy(&token, &buyer, 1_000_000, total_cost - 1).await;
+    assert_program_error(result, ZeroglazeError::SlippageExceeded);
+    assert_eq!(env.curve_reserves(&token).await.tokens_sold, 0);
+}
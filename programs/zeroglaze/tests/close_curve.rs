@@ -0,0 +1,218 @@
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::{HOLDER_REWARD_CLAIM_WINDOW_SECS, MAX_CLOSE_REWARD_DUST_LAMPORTS};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze_test_harness::*;
+
+async fn setup() -> (TestEnv, TokenAccounts, Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(100_000_000_000).await;
+    env.buy(&token, &buyer, 100_000_000, u64::MAX).await.unwrap();
+    (env, token, creator)
+}
+
+/// A token bought past the graduation threshold
+async fn setup_graduated() -> (TestEnv, TokenAccounts, Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let whale = env.funded_keypair(200_000_000_000).await;
+    env.buy(&token, &whale, 795_000_000, u64::MAX).await.unwrap();
+    assert!(env.curve_reserves(&token).await.is_graduated());
+    (env, token, creator)
+}
+
+/// A graduated token whose liquidity has moved to the DEX
+async fn setup_migrated() -> (TestEnv, TokenAccounts, Keypair) {
+    let (mut env, token, creator) = setup_graduated().await;
+    let authority = env.payer();
+    env.withdraw_graduated_liquidity(&token, &authority).await.unwrap();
+    (env, token, creator)
+}
+
+/// A migrated token whose graduating buy was live, so `alice` has holder
+/// rewards to claim
+async fn setup_with_rewards() -> (TestEnv, TokenAccounts, Keypair, Keypair) {
+    let mut env = TestEnv::new().await;
+    let authority = env.payer();
+    env.set_holder_reward_share(&authority, 5_000).await.unwrap();
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let alice = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &alice, 10_000_000, u64::MAX).await.unwrap();
+
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    let whale = env.funded_keypair(200_000_000_000).await;
+    env.buy(&token, &whale, 785_000_000, u64::MAX).await.unwrap();
+    let reserves = env.curve_reserves(&token).await;
+    assert!(reserves.is_graduated());
+    assert!(reserves.holder_rewards_outstanding() > MAX_CLOSE_REWARD_DUST_LAMPORTS);
+
+    env.withdraw_graduated_liquidity(&token, &authority).await.unwrap();
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    (env, token, creator, alice)
+}
+
+async fn account_exists(env: &mut TestEnv, address: &solana_sdk::pubkey::Pubkey) -> bool {
+    env.context.banks_client.get_account(*address).await.unwrap().is_some()
+}
+
+#[tokio::test]
+async fn close_returns_rent_and_records_history() {
+    let (mut env, token, creator) = setup_migrated().await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+
+    let reserves = env.curve_reserves(&token).await;
+    let metadata = env.token_metadata(&token).await;
+    let closed = [
+        token.bonding_curve,
+        token.curve_reserves,
+        token.token_metadata,
+        token.stream_history,
+        token.curve_token_account,
+        token.curve_sol_vault,
+        token.creator_fee_wallet,
+        token.holder_rewards_pool,
+    ];
+    let mut reclaimable = 0;
+    for address in closed {
+        reclaimable += env.lamports(&address).await;
+    }
+    let record_rent = env
+        .rent_exempt_minimum(8 + <zeroglaze::state::GraduatedToken as anchor_lang::Space>::INIT_SPACE)
+        .await;
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.close_curve(&token, &creator).await.unwrap();
+
+    // The creator is also the fee authority here, so it funds the record
+    let creator_after = env.lamports(&creator.pubkey()).await;
+    assert_eq!(creator_after, creator_before + reclaimable - record_rent);
+    for address in closed {
+        assert!(!account_exists(&mut env, &address).await, "{address} still open");
+    }
+
+    let record = env.graduated_token(&token).await;
+    assert_eq!(record.token_mint, token.token_mint);
+    assert_eq!(record.creator, creator.pubkey());
+    assert_eq!(record.token_symbol, metadata.token_symbol);
+    assert_eq!(record.tokens_sold, reserves.tokens_sold);
+    assert_eq!(record.total_volume, reserves.total_volume);
+    assert_eq!(record.creator_fees_collected, reserves.creator_fees_collected);
+}
+
+#[tokio::test]
+async fn close_requires_graduation() {
+    let (mut env, token, creator) = setup().await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+
+    let result = env.close_curve(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::TokenNotGraduated);
+}
+
+#[tokio::test]
+async fn close_requires_drained_liquidity() {
+    let (mut env, token, creator) = setup_graduated().await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+
+    // Graduated, but the liquidity has not been withdrawn for the DEX
+    let result = env.close_curve(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::CurveNotDrained);
+}
+
+#[tokio::test]
+async fn close_requires_creator_fees_withdrawn() {
+    let (mut env, token, creator) = setup_migrated().await;
+
+    let result = env.close_curve(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::CurveNotDrained);
+}
+
+#[tokio::test]
+async fn close_requires_holder_rewards_claimed() {
+    let (mut env, token, creator, alice) = setup_with_rewards().await;
+
+    let result = env.close_curve(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::CurveNotDrained);
+
+    env.claim_holder_rewards(&token, &alice).await.unwrap();
+    env.close_curve(&token, &creator).await.unwrap();
+}
+
+#[tokio::test]
+async fn close_after_claim_window_when_holder_moved_tokens_away() {
+    let (mut env, token, creator, alice) = setup_with_rewards().await;
+    let elsewhere = env.funded_keypair(1_000_000_000).await;
+    env.transfer_tokens(&token, &alice, &elsewhere.pubkey(), 10_000_000).await;
+
+    // Alice can no longer claim, but her rewards still count as owed until
+    // the claim window runs out
+    let result = env.claim_holder_rewards(&token, &alice).await;
+    assert_program_error(result, ZeroglazeError::NoRewardsToClaim);
+    let result = env.close_curve(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::CurveNotDrained);
+
+    env.advance_clock(HOLDER_REWARD_CLAIM_WINDOW_SECS).await;
+    let pool_rent = env.rent_exempt_minimum(0).await;
+    let remainder = env.lamports(&token.holder_rewards_pool).await - pool_rent;
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    env.close_curve(&token, &creator).await.unwrap();
+
+    assert!(remainder > MAX_CLOSE_REWARD_DUST_LAMPORTS);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + remainder);
+}
+
+#[tokio::test]
+async fn forfeited_rewards_do_not_block_close() {
+    let (mut env, token, creator, alice) = setup_with_rewards().await;
+    let elsewhere = env.funded_keypair(1_000_000_000).await;
+    env.transfer_tokens(&token, &alice, &elsewhere.pubkey(), 5_000_000).await;
+
+    // Claiming settles the smaller stake; the other half is forfeited
+    env.claim_holder_rewards(&token, &alice).await.unwrap();
+    let reserves = env.curve_reserves(&token).await;
+    assert!(reserves.holder_rewards_forfeited > 0);
+    assert!(reserves.holder_rewards_outstanding() <= MAX_CLOSE_REWARD_DUST_LAMPORTS);
+
+    let pool_rent = env.rent_exempt_minimum(0).await;
+    let remainder = env.lamports(&token.holder_rewards_pool).await - pool_rent;
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    env.close_curve(&token, &creator).await.unwrap();
+
+    assert!(remainder >= reserves.holder_rewards_forfeited);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + remainder);
+}
+
+#[tokio::test]
+async fn close_sends_reward_dust_to_platform() {
+    let (mut env, token, creator) = setup_migrated().await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    env.airdrop(&token.holder_rewards_pool, MAX_CLOSE_REWARD_DUST_LAMPORTS).await;
+
+    let pool_rent = env.rent_exempt_minimum(0).await;
+    assert_eq!(
+        env.lamports(&token.holder_rewards_pool).await,
+        pool_rent + MAX_CLOSE_REWARD_DUST_LAMPORTS
+    );
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    env.close_curve(&token, &creator).await.unwrap();
+
+    assert_eq!(
+        env.lamports(&platform_fee_wallet).await,
+        platform_before + MAX_CLOSE_REWARD_DUST_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn only_fee_authority_can_close() {
+    let (mut env, token, creator) = setup_migrated().await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    let stranger = env.funded_keypair(1_000_000_000).await;
+
+    let result = env.close_curve(&token, &stranger).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
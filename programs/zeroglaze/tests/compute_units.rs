@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze_test_harness::*;
+
+// Compute units per instruction under representative states, checked
+// against tests/compute_units.baseline. Only the compiled program is metered:
+//
+//   cargo build-sbf
+//   SBF_OUT_DIR=target/deploy cargo test --test compute_units -- --nocapture
+//
+// Set UPDATE_CU_BASELINE=1 to rewrite the baseline with the measured values.
+// Without an SBF build the test is skipped, unless ZEROGLAZE_CHECK_CU=1 (set
+// by CI) makes a missing program or baseline file a failure.
+
+/// Allowed growth over the baseline. PDA bump searches depend on the random
+/// test keys, so usage varies slightly between runs.
+const TOLERANCE_BPS: u64 = 300;
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compute_units.baseline")
+}
+
+fn check_required() -> bool {
+    std::env::var_os("ZEROGLAZE_CHECK_CU").is_some()
+}
+
+/// `name units` per line; `#` starts a comment
+fn read_baseline() -> BTreeMap<String, u64> {
+    let contents = std::fs::read_to_string(baseline_path())
+        .unwrap_or_else(|e| panic!("reading {}: {e}", baseline_path().display()));
+    contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, units) = line.split_once(char::is_whitespace).expect("malformed baseline line");
+            (name.to_string(), units.trim().parse().expect("malformed baseline units"))
+        })
+        .collect()
+}
+
+fn write_baseline(measured: &BTreeMap<String, u64>) {
+    let mut contents = String::from(
+        "# Compute units per instruction, checked by tests/compute_units.rs\n\
+         # Regenerate with UPDATE_CU_BASELINE=1 against a build-sbf output\n",
+    );
+    for (name, units) in measured {
+        contents.push_str(&format!("{name} {units}\n"));
+    }
+    std::fs::write(baseline_path(), contents).unwrap();
+}
+
+struct Bench {
+    env: TestEnv,
+    measured: BTreeMap<String, u64>,
+}
+
+impl Bench {
+    async fn record(&mut self, name: &str, ix: solana_sdk::instruction::Instruction, signer: &Keypair) {
+        // Identical transactions would be deduplicated
+        self.env.advance_clock(1).await;
+        let units = self
+            .env
+            .process_metered(&[ix], &[signer])
+            .await
+            .unwrap_or_else(|e| panic!("{name} failed: {e}"));
+        self.measured.insert(name.to_string(), units);
+    }
+}
+
+#[tokio::test]
+async fn compute_units_within_baseline() {
+    let Some(program_test) = bpf_program_test() else {
+        assert!(!check_required(), "ZEROGLAZE_CHECK_CU is set but zeroglaze.so was not found in SBF_OUT_DIR");
+        eprintln!("skipping: zeroglaze.so not found in SBF_OUT_DIR");
+        return;
+    };
+    let mut bench = Bench {
+        env: TestEnv::with_program_test(program_test).await,
+        measured: BTreeMap::new(),
+    };
+
+    let creator = bench.env.funded_keypair(10_000_000_000).await;
+    let platform_fee_wallet = bench.env.platform_fee_wallet.pubkey();
+    let ix = instructions::create_token(&creator.pubkey(), &platform_fee_wallet, TestEnv::create_token_args(false));
+    bench.record("create_token", ix, &creator).await;
+    let token = TokenAccounts::new(&creator.pubkey());
+
+    // First buy creates the buyer's token account and buyer record
+    let buyer = bench.env.funded_keypair(10_000_000_000).await;
+    let ix = instructions::buy_tokens(&token, &platform_fee_wallet, &buyer.pubkey(), 1_000_000, u64::MAX, None);
+    bench.record("buy_tokens_first", ix, &buyer).await;
+
+    let ix = instructions::buy_tokens(&token, &platform_fee_wallet, &buyer.pubkey(), 1_000_000, u64::MAX, None);
+    bench.record("buy_tokens_repeat", ix, &buyer).await;
+
+    let ix = instructions::sell_tokens(&token, &platform_fee_wallet, &buyer.pubkey(), 500_000, 0, None);
+    bench.record("sell_tokens", ix, &buyer).await;
+
+    let ix = instructions::withdraw_creator_fees(&token, &creator.pubkey(), &[], None, None);
+    bench.record("withdraw_creator_fees", ix, &creator).await;
+
+    // Take the curve to just under the graduation threshold, then cross it
+    let whale = bench.env.funded_keypair(200_000_000_000).await;
+    bench.env.buy(&token, &whale, 775_000_000, u64::MAX).await.unwrap();
+    let ix = instructions::buy_tokens(&token, &platform_fee_wallet, &whale.pubkey(), 20_000_000, u64::MAX, None);
+    bench.record("buy_tokens_graduating", ix, &whale).await;
+    assert!(bench.env.curve_reserves(&token).await.is_graduated());
+
+    let measured = bench.measured;
+    for (name, units) in &measured {
+        println!("{name:<24} {units:>7} CU");
+    }
+
+    if std::env::var_os("UPDATE_CU_BASELINE").is_some() {
+        write_baseline(&measured);
+        return;
+    }
+
+    let baseline = read_baseline();
+    let mut failures = Vec::new();
+    for (name, &units) in &measured {
+        match baseline.get(name) {
+            None => failures.push(format!("{name}: no baseline entry")),
+            Some(&expected) if units > expected + expected * TOLERANCE_BPS / 10_000 => {
+                failures.push(format!("{name}: {units} CU, baseline {expected} CU"))
+            }
+            Some(_) => {}
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "compute units regressed (rerun with UPDATE_CU_BASELINE=1 if intended):\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,96 @@
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::Space;
+use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, Mint};
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::{
+    BONDING_CURVE_VERSION, CREATOR_SUPPLY, CURVE_SUPPLY, VIRTUAL_SOL_INITIAL, VIRTUAL_TOKEN_INITIAL,
+};
+use zeroglaze::state::{BondingCurve, CurveReserves, StreamHistory, TokenMetadata};
+use zeroglaze_test_harness::*;
+
+fn creator_token_account(token: &TokenAccounts) -> solana_sdk::pubkey::Pubkey {
+    anchor_spl::associated_token::get_associated_token_address(&token.creator, &token.token_mint)
+}
+
+#[tokio::test]
+async fn create_initializes_curve_accounts() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+
+    let curve = env.bonding_curve(&token).await;
+    assert_eq!(curve.creator, creator.pubkey());
+    assert_eq!(curve.token_mint, token.token_mint);
+    assert_eq!(curve.fee_authority, creator.pubkey());
+    assert!(!curve.freeze_creator_allocation);
+    assert!(!curve.is_live_streaming);
+    assert_eq!(curve.version, BONDING_CURVE_VERSION);
+
+    let reserves = env.curve_reserves(&token).await;
+    assert_eq!(reserves.bonding_curve, token.bonding_curve);
+    assert_eq!(reserves.virtual_sol_reserves, VIRTUAL_SOL_INITIAL);
+    assert_eq!(reserves.virtual_token_reserves, VIRTUAL_TOKEN_INITIAL);
+    assert_eq!(reserves.real_sol_reserves, 0);
+    assert_eq!(reserves.real_token_reserves, CURVE_SUPPLY);
+    assert_eq!(reserves.tokens_sold, 0);
+    assert!(!reserves.is_graduated());
+
+    let metadata = env.token_metadata(&token).await;
+    assert_eq!(metadata.bonding_curve, token.bonding_curve);
+    assert_eq!(metadata.token_name, "Zeroglaze Test");
+    assert_eq!(metadata.token_symbol, "ZGT");
+
+    assert_eq!(env.token_balance(&token.curve_token_account).await, CURVE_SUPPLY);
+    assert_eq!(env.token_balance(&creator_token_account(&token)).await, CREATOR_SUPPLY);
+    assert_eq!(env.platform_state().await.total_tokens_launched, 1);
+
+    // Without an initial buy the creator only pays rent for the new accounts
+    let mut rent = 0;
+    for space in [
+        8 + BondingCurve::INIT_SPACE,
+        8 + CurveReserves::LEN,
+        8 + TokenMetadata::INIT_SPACE,
+        8 + StreamHistory::INIT_SPACE,
+        Mint::LEN,
+        SplTokenAccount::LEN,
+        SplTokenAccount::LEN,
+        0,
+        0,
+        0,
+    ] {
+        rent += env.rent_exempt_minimum(space).await;
+    }
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before - rent);
+}
+
+#[tokio::test]
+async fn unfrozen_creator_allocation_is_transferable() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    assert!(!env.token_account(&creator_token_account(&token)).await.is_frozen());
+
+    let recipient = Keypair::new();
+    env.try_transfer_tokens(&token, &creator, &recipient.pubkey(), 1_000_000)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.token_balance(&creator_token_account(&token)).await,
+        CREATOR_SUPPLY - 1_000_000
+    );
+}
+
+#[tokio::test]
+async fn frozen_creator_allocation_cannot_move() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, true).await.unwrap();
+    assert!(env.bonding_curve(&token).await.freeze_creator_allocation);
+    assert!(env.token_account(&creator_token_account(&token)).await.is_frozen());
+
+    let recipient = Keypair::new();
+    let result = env.try_transfer_tokens(&token, &creator, &recipient.pubkey(), 1_000_000).await;
+    assert!(result.is_err());
+    assert_eq!(env.token_balance(&creator_token_account(&token)).await, CREATOR_SUPPLY);
+}
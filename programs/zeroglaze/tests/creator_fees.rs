@@ -0,0 +1,131 @@
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::state::FeeRecipient;
+use zeroglaze_test_harness::*;
+
+async fn setup_with_fees(
+    env: &mut TestEnv,
+    fee_recipients: Vec<FeeRecipient>,
+    creator: &Keypair,
+) -> (TokenAccounts, u64) {
+    let mut args = TestEnv::create_token_args(false);
+    args.fee_recipients = fee_recipients;
+    let token = env.create_token_with(creator, args).await.unwrap();
+    let buyer = env.funded_keypair(100_000_000_000).await;
+    env.buy(&token, &buyer, 100_000_000, u64::MAX).await.unwrap();
+    let fees = env.curve_reserves(&token).await.creator_fees_collected;
+    assert!(fees > 0);
+    (token, fees)
+}
+
+/// Pending fees recorded on the curve match what sits above rent in the fee wallet
+async fn assert_reconciled(env: &mut TestEnv, token: &TokenAccounts) {
+    let curve = env.curve_reserves(token).await;
+    let rent = env.rent_exempt_minimum(0).await;
+    assert_eq!(
+        curve.creator_fees_pending(),
+        env.lamports(&token.creator_fee_wallet).await - rent
+    );
+}
+
+#[tokio::test]
+async fn partial_withdrawals_track_withdrawn_fees() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let (token, fees) = setup_with_fees(&mut env, vec![], &creator).await;
+
+    let first = fees / 3;
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.withdraw_creator_fees_to(&token, &creator, Some(first), None).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.creator_fees_withdrawn, first);
+    assert_eq!(curve.creator_fees_pending(), fees - first);
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before + first);
+    assert_reconciled(&mut env, &token).await;
+
+    // The rest defaults to everything still pending
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.creator_fees_withdrawn, fees);
+    assert_eq!(curve.creator_fees_pending(), 0);
+    assert_reconciled(&mut env, &token).await;
+
+    // Later trades accrue on top of what was withdrawn
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &buyer, 10_000_000, u64::MAX).await.unwrap();
+    let curve = env.curve_reserves(&token).await;
+    assert!(curve.creator_fees_pending() > 0);
+    assert_reconciled(&mut env, &token).await;
+}
+
+#[tokio::test]
+async fn withdraw_to_destination() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let (token, fees) = setup_with_fees(&mut env, vec![], &creator).await;
+    let cold_wallet = env.funded_keypair(1_000_000_000).await.pubkey();
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.withdraw_creator_fees_to(&token, &creator, None, Some(&cold_wallet)).await.unwrap();
+
+    assert_eq!(env.lamports(&cold_wallet).await, 1_000_000_000 + fees);
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before);
+    assert_reconciled(&mut env, &token).await;
+}
+
+#[tokio::test]
+async fn destination_only_redirects_the_creator_share() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let collaborator = env.funded_keypair(1_000_000_000).await.pubkey();
+    let fee_recipients = vec![
+        FeeRecipient { wallet: creator.pubkey(), weight_bps: 7_500 },
+        FeeRecipient { wallet: collaborator, weight_bps: 2_500 },
+    ];
+    let (token, fees) = setup_with_fees(&mut env, fee_recipients, &creator).await;
+    let cold_wallet = env.funded_keypair(1_000_000_000).await.pubkey();
+
+    let amount = fees / 2;
+    env.withdraw_creator_fees_to(&token, &creator, Some(amount), Some(&cold_wallet))
+        .await
+        .unwrap();
+
+    let collaborator_share = amount * 2_500 / 10_000;
+    assert_eq!(env.lamports(&collaborator).await, 1_000_000_000 + collaborator_share);
+    assert_eq!(env.lamports(&cold_wallet).await, 1_000_000_000 + amount - collaborator_share);
+    assert_reconciled(&mut env, &token).await;
+}
+
+#[tokio::test]
+async fn cannot_withdraw_more_than_pending() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let (token, fees) = setup_with_fees(&mut env, vec![], &creator).await;
+
+    let result = env.withdraw_creator_fees_to(&token, &creator, Some(fees + 1), None).await;
+    assert_program_error(result, ZeroglazeError::InsufficientCreatorFees);
+
+    let result = env.withdraw_creator_fees_to(&token, &creator, Some(0), None).await;
+    assert_program_error(result, ZeroglazeError::InvalidAmount);
+}
+
+#[tokio::test]
+async fn unrecorded_lamports_are_not_withdrawable() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let (token, fees) = setup_with_fees(&mut env, vec![], &creator).await;
+
+    // A stray transfer into the fee wallet is not a creator fee
+    env.airdrop(&token.creator_fee_wallet, 5_000_000).await;
+
+    let result = env.withdraw_creator_fees_to(&token, &creator, Some(fees + 1), None).await;
+    assert_program_error(result, ZeroglazeError::InsufficientCreatorFees);
+
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.creator_fees_withdrawn, fees);
+
+    let result = env.withdraw_creator_fees(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::NoFeesToWithdraw);
+}
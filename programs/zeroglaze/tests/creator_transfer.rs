@@ -0,0 +1,123 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::state::FeeRecipient;
+use zeroglaze_test_harness::*;
+
+async fn setup() -> (TestEnv, TokenAccounts, Keypair, Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let new_wallet = env.funded_keypair(1_000_000_000).await;
+    (env, token, creator, new_wallet)
+}
+
+async fn accrue_fees(env: &mut TestEnv, token: &TokenAccounts) {
+    let buyer = env.funded_keypair(100_000_000_000).await;
+    env.buy(token, &buyer, 100_000_000, u64::MAX).await.unwrap();
+}
+
+#[tokio::test]
+async fn accepted_transfer_moves_fee_withdrawal() {
+    let (mut env, token, creator, new_wallet) = setup().await;
+    accrue_fees(&mut env, &token).await;
+
+    env.transfer_creator(&token, &creator, &new_wallet.pubkey()).await.unwrap();
+    let curve = env.bonding_curve(&token).await;
+    assert_eq!(curve.fee_authority, creator.pubkey());
+    assert_eq!(curve.pending_fee_authority, new_wallet.pubkey());
+
+    // Nothing changes until the new wallet accepts
+    let result = env.withdraw_creator_fees(&token, &new_wallet).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+
+    env.accept_creator(&token, &new_wallet).await.unwrap();
+    let curve = env.bonding_curve(&token).await;
+    assert_eq!(curve.fee_authority, new_wallet.pubkey());
+    assert_eq!(curve.pending_fee_authority, Pubkey::default());
+    // PDA seeds still derive from the original creator
+    assert_eq!(curve.creator, creator.pubkey());
+
+    let result = env.withdraw_creator_fees(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+
+    let pending = env.curve_reserves(&token).await.creator_fees_pending();
+    let before = env.lamports(&new_wallet.pubkey()).await;
+    env.withdraw_creator_fees(&token, &new_wallet).await.unwrap();
+    assert_eq!(env.lamports(&new_wallet.pubkey()).await, before + pending);
+}
+
+#[tokio::test]
+async fn only_pending_authority_can_accept() {
+    let (mut env, token, creator, new_wallet) = setup().await;
+    let stranger = env.funded_keypair(1_000_000_000).await;
+
+    env.transfer_creator(&token, &creator, &new_wallet.pubkey()).await.unwrap();
+
+    let result = env.accept_creator(&token, &stranger).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+    let result = env.accept_creator(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
+
+#[tokio::test]
+async fn only_fee_authority_can_propose() {
+    let (mut env, token, _, new_wallet) = setup().await;
+
+    let result = env.transfer_creator(&token, &new_wallet, &new_wallet.pubkey()).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
+
+#[tokio::test]
+async fn pending_transfer_can_be_cancelled() {
+    let (mut env, token, creator, new_wallet) = setup().await;
+
+    env.transfer_creator(&token, &creator, &new_wallet.pubkey()).await.unwrap();
+    env.transfer_creator(&token, &creator, &Pubkey::default()).await.unwrap();
+
+    let result = env.accept_creator(&token, &new_wallet).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+    assert_eq!(env.bonding_curve(&token).await.fee_authority, creator.pubkey());
+}
+
+#[tokio::test]
+async fn new_authority_needs_creator_consent_to_cut_their_share() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let collaborator = env.funded_keypair(1_000_000_000).await;
+    let mut args = TestEnv::create_token_args(false);
+    args.fee_recipients = vec![
+        FeeRecipient { wallet: creator.pubkey(), weight_bps: 8_000 },
+        FeeRecipient { wallet: collaborator.pubkey(), weight_bps: 2_000 },
+    ];
+    let token = env.create_token_with(&creator, args).await.unwrap();
+    let new_wallet = env.funded_keypair(1_000_000_000).await;
+
+    env.transfer_creator(&token, &creator, &new_wallet.pubkey()).await.unwrap();
+    env.accept_creator(&token, &new_wallet).await.unwrap();
+
+    let updated = vec![
+        FeeRecipient { wallet: new_wallet.pubkey(), weight_bps: 8_000 },
+        FeeRecipient { wallet: collaborator.pubkey(), weight_bps: 2_000 },
+    ];
+    let result = env
+        .update_fee_recipients(&token, &new_wallet, updated.clone(), &[])
+        .await;
+    assert_program_error(result, ZeroglazeError::MissingRecipientConsent);
+
+    // Collaborators still have to agree to a smaller share too
+    let result = env
+        .update_fee_recipients(
+            &token,
+            &new_wallet,
+            vec![FeeRecipient { wallet: new_wallet.pubkey(), weight_bps: 10_000 }],
+            &[&creator],
+        )
+        .await;
+    assert_program_error(result, ZeroglazeError::MissingRecipientConsent);
+
+    env.update_fee_recipients(&token, &new_wallet, updated.clone(), &[&creator])
+        .await
+        .unwrap();
+    assert_eq!(env.bonding_curve(&token).await.fee_recipients, updated);
+}
@@ -0,0 +1,180 @@
+use proptest::prelude::*;
+use zeroglaze::constants::{
+    CURVE_SUPPLY, MAX_CREATOR_FEE_BPS, VIRTUAL_SOL_INITIAL, VIRTUAL_TOKEN_INITIAL,
+};
+use zeroglaze::utils::{calculate_buy_cost, calculate_sell_output, calculate_tokens_for_sol};
+
+// Random trade sequences against the curve math, mirroring how buy_tokens
+// and sell_tokens move the virtual reserves.
+
+#[derive(Clone, Debug)]
+enum Trade {
+    Buy(u64),
+    Sell(u64),
+}
+
+fn trade() -> impl Strategy<Value = Trade> {
+    prop_oneof![
+        (1..=CURVE_SUPPLY).prop_map(Trade::Buy),
+        (1..=CURVE_SUPPLY).prop_map(Trade::Sell),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Curve {
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    tokens_sold: u64,
+}
+
+impl Curve {
+    fn new() -> Self {
+        Self {
+            virtual_sol_reserves: VIRTUAL_SOL_INITIAL,
+            virtual_token_reserves: VIRTUAL_TOKEN_INITIAL,
+            tokens_sold: 0,
+        }
+    }
+
+    fn k(&self) -> u128 {
+        self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128
+    }
+
+    /// Whether `self` prices a token at least as high as `other`
+    fn price_at_least(&self, other: &Curve) -> bool {
+        self.virtual_sol_reserves as u128 * other.virtual_token_reserves as u128
+            >= other.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128
+    }
+
+    fn buy(&mut self, token_amount: u64, fee_bps: u16) -> u64 {
+        let (sol_required, _, _, total_cost) =
+            calculate_buy_cost(self.virtual_sol_reserves, self.virtual_token_reserves, token_amount, fee_bps)
+                .expect("valid buy failed");
+        self.virtual_sol_reserves += sol_required;
+        self.virtual_token_reserves -= token_amount;
+        self.tokens_sold += token_amount;
+        total_cost
+    }
+
+    fn sell(&mut self, token_amount: u64, fee_bps: u16) -> u64 {
+        let (sol_to_return, _, _, net_output) =
+            calculate_sell_output(self.virtual_sol_reserves, self.virtual_token_reserves, token_amount, fee_bps)
+                .expect("valid sell failed");
+        self.virtual_sol_reserves -= sol_to_return;
+        self.virtual_token_reserves += token_amount;
+        self.tokens_sold -= token_amount;
+        net_output
+    }
+}
+
+proptest! {
+    #[test]
+    fn trade_sequences_keep_curve_consistent(
+        trades in prop::collection::vec(trade(), 1..64),
+        fee_bps in 0..=MAX_CREATOR_FEE_BPS,
+    ) {
+        let mut curve = Curve::new();
+        for trade in trades {
+            let before = curve;
+            match trade {
+                // Clamp to what the curve can sell or the traders hold
+                Trade::Buy(amount) => {
+                    let amount = amount.min(CURVE_SUPPLY - curve.tokens_sold);
+                    if amount == 0 {
+                        continue;
+                    }
+                    curve.buy(amount, fee_bps);
+                    prop_assert!(curve.price_at_least(&before));
+                }
+                Trade::Sell(amount) => {
+                    let amount = amount.min(curve.tokens_sold);
+                    if amount == 0 {
+                        continue;
+                    }
+                    curve.sell(amount, fee_bps);
+                    prop_assert!(before.price_at_least(&curve));
+                }
+            }
+            prop_assert!(curve.k() >= before.k(), "k shrank: {:?} -> {:?}", before, curve);
+            prop_assert!(curve.virtual_sol_reserves >= VIRTUAL_SOL_INITIAL);
+        }
+    }
+
+    #[test]
+    fn buy_then_sell_never_profits(
+        prior_trades in prop::collection::vec(1..CURVE_SUPPLY / 8, 0..8),
+        amount in 1..=CURVE_SUPPLY / 2,
+        fee_bps in 0..=MAX_CREATOR_FEE_BPS,
+    ) {
+        let mut curve = Curve::new();
+        for prior in prior_trades {
+            let prior = prior.min((CURVE_SUPPLY / 2).saturating_sub(curve.tokens_sold));
+            if prior > 0 {
+                curve.buy(prior, fee_bps);
+            }
+        }
+
+        let paid = curve.buy(amount, fee_bps);
+        let received = curve.sell(amount, fee_bps);
+        prop_assert!(received <= paid, "paid {} but received {}", paid, received);
+    }
+
+    #[test]
+    fn quotes_are_monotonic_in_amount(
+        sold in 0..CURVE_SUPPLY,
+        amount in 1..CURVE_SUPPLY,
+        fee_bps in 0..=MAX_CREATOR_FEE_BPS,
+    ) {
+        let mut curve = Curve::new();
+        if sold > 0 {
+            curve.buy(sold, fee_bps);
+        }
+
+        let amount = amount.min(CURVE_SUPPLY - sold);
+        prop_assume!(amount > 1);
+        let smaller = calculate_buy_cost(curve.virtual_sol_reserves, curve.virtual_token_reserves, amount - 1, fee_bps).unwrap();
+        let larger = calculate_buy_cost(curve.virtual_sol_reserves, curve.virtual_token_reserves, amount, fee_bps).unwrap();
+        prop_assert!(larger.3 >= smaller.3);
+
+        let amount = amount.min(sold);
+        prop_assume!(amount > 1);
+        let smaller = calculate_sell_output(curve.virtual_sol_reserves, curve.virtual_token_reserves, amount - 1, fee_bps).unwrap();
+        let larger = calculate_sell_output(curve.virtual_sol_reserves, curve.virtual_token_reserves, amount, fee_bps).unwrap();
+        prop_assert!(larger.3 >= smaller.3);
+    }
+
+    #[test]
+    fn tokens_for_sol_never_overcharge(
+        sold in 0..CURVE_SUPPLY,
+        sol_amount in 0..100_000_000_000u64,
+        fee_bps in 0..=MAX_CREATOR_FEE_BPS,
+    ) {
+        let mut curve = Curve::new();
+        if sold > 0 {
+            curve.buy(sold, fee_bps);
+        }
+
+        let tokens = calculate_tokens_for_sol(curve.virtual_sol_reserves, curve.virtual_token_reserves, sol_amount).unwrap();
+        prop_assert!(tokens < curve.virtual_token_reserves);
+        if tokens > 0 {
+            let (sol_required, _, _, _) = calculate_buy_cost(curve.virtual_sol_reserves, curve.virtual_token_reserves, tokens, fee_bps).unwrap();
+            prop_assert!(sol_required <= sol_amount, "{} tokens cost {} for {}", tokens, sol_required, sol_amount);
+        }
+    }
+
+    #[test]
+    fn tokens_for_sol_never_panics(
+        virtual_sol_reserves in prop_oneof![Just(0u64), any::<u64>()],
+        virtual_token_reserves in prop_oneof![Just(0u64), any::<u64>()],
+        sol_amount in prop_oneof![Just(0u64), any::<u64>()],
+    ) {
+        let _ = calculate_tokens_for_sol(virtual_sol_reserves, virtual_token_reserves, sol_amount);
+    }
+}
+
+#[test]
+fn tokens_for_sol_rejects_empty_reserves() {
+    assert!(calculate_tokens_for_sol(0, 0, 0).is_err());
+    assert!(calculate_tokens_for_sol(0, VIRTUAL_TOKEN_INITIAL, 0).is_err());
+    assert_eq!(calculate_tokens_for_sol(0, 0, 1_000).unwrap(), 0);
+}
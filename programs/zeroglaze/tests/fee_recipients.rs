@@ -0,0 +1,173 @@
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::state::FeeRecipient;
+use zeroglaze_test_harness::*;
+
+async fn setup(split: &[(u16, bool)]) -> (TestEnv, TokenAccounts, Keypair, Vec<Keypair>) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let mut collaborators = Vec::new();
+    let mut fee_recipients = Vec::new();
+    for &(weight_bps, is_creator) in split {
+        let wallet = if is_creator {
+            creator.pubkey()
+        } else {
+            let collaborator = env.funded_keypair(100_000_000).await;
+            let wallet = collaborator.pubkey();
+            collaborators.push(collaborator);
+            wallet
+        };
+        fee_recipients.push(FeeRecipient { wallet, weight_bps });
+    }
+
+    let mut args = TestEnv::create_token_args(false);
+    args.fee_recipients = fee_recipients;
+    let token = env.create_token_with(&creator, args).await.unwrap();
+    (env, token, creator, collaborators)
+}
+
+/// Generate creator fees with a buy and return the withdrawable amount
+async fn accrue_fees(env: &mut TestEnv, token: &TokenAccounts) -> u64 {
+    let buyer = env.funded_keypair(100_000_000_000).await;
+    env.buy(token, &buyer, 100_000_000, u64::MAX).await.unwrap();
+    let rent = env.rent_exempt_minimum(0).await;
+    env.lamports(&token.creator_fee_wallet).await - rent
+}
+
+#[tokio::test]
+async fn withdraw_splits_fees_by_weight() {
+    let (mut env, token, creator, collaborators) = setup(&[(6_000, true), (4_000, false)]).await;
+    let available = accrue_fees(&mut env, &token).await;
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    let collaborator_before = env.lamports(&collaborators[0].pubkey()).await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+
+    let collaborator_share = available * 4_000 / 10_000;
+    assert_eq!(
+        env.lamports(&collaborators[0].pubkey()).await - collaborator_before,
+        collaborator_share
+    );
+    assert_eq!(
+        env.lamports(&creator.pubkey()).await - creator_before,
+        available - collaborator_share
+    );
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, env.rent_exempt_minimum(0).await);
+}
+
+#[tokio::test]
+async fn creator_can_give_away_the_whole_split() {
+    let (mut env, token, creator, collaborators) = setup(&[(5_000, false), (5_000, false)]).await;
+    let available = accrue_fees(&mut env, &token).await;
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    let before = [
+        env.lamports(&collaborators[0].pubkey()).await,
+        env.lamports(&collaborators[1].pubkey()).await,
+    ];
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+
+    let gained = [
+        env.lamports(&collaborators[0].pubkey()).await - before[0],
+        env.lamports(&collaborators[1].pubkey()).await - before[1],
+    ];
+    assert_eq!(gained[0] + gained[1], available);
+    assert!(gained[0].abs_diff(gained[1]) <= 1);
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before);
+}
+
+#[tokio::test]
+async fn destination_rejected_when_creator_has_no_share() {
+    let (mut env, token, creator, _) = setup(&[(5_000, false), (5_000, false)]).await;
+    accrue_fees(&mut env, &token).await;
+    let cold_wallet = env.funded_keypair(1_000_000_000).await.pubkey();
+
+    let result = env.withdraw_creator_fees_to(&token, &creator, None, Some(&cold_wallet)).await;
+    assert_program_error(result, ZeroglazeError::UnusedDestination);
+}
+
+#[tokio::test]
+async fn withdraw_requires_recipient_wallets_in_order() {
+    let (mut env, token, creator, collaborators) = setup(&[(6_000, true), (4_000, false)]).await;
+    accrue_fees(&mut env, &token).await;
+
+    // Missing recipients
+    let ix = instructions::withdraw_creator_fees(&token, &creator.pubkey(), &[], None, None);
+    let result = env.process(&[ix], &[&creator]).await;
+    assert_program_error(result, ZeroglazeError::InvalidFeeRecipients);
+
+    // Swapped wallets
+    let swapped = [
+        FeeRecipient { wallet: collaborators[0].pubkey(), weight_bps: 6_000 },
+        FeeRecipient { wallet: creator.pubkey(), weight_bps: 4_000 },
+    ];
+    let ix = instructions::withdraw_creator_fees(&token, &creator.pubkey(), &swapped, None, None);
+    let result = env.process(&[ix], &[&creator]).await;
+    assert_program_error(result, ZeroglazeError::InvalidFeeRecipients);
+}
+
+#[tokio::test]
+async fn invalid_split_rejected_at_launch() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let mut args = TestEnv::create_token_args(false);
+    args.fee_recipients = vec![
+        FeeRecipient { wallet: creator.pubkey(), weight_bps: 6_000 },
+        FeeRecipient { wallet: Keypair::new().pubkey(), weight_bps: 3_000 },
+    ];
+
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::InvalidFeeRecipients);
+}
+
+#[tokio::test]
+async fn reducing_a_share_requires_recipient_consent() {
+    let (mut env, token, creator, collaborators) = setup(&[(6_000, true), (4_000, false)]).await;
+    let collaborator = &collaborators[0];
+    let reduced = vec![
+        FeeRecipient { wallet: creator.pubkey(), weight_bps: 9_000 },
+        FeeRecipient { wallet: collaborator.pubkey(), weight_bps: 1_000 },
+    ];
+
+    let result = env
+        .update_fee_recipients(&token, &creator, reduced.clone(), &[])
+        .await;
+    assert_program_error(result, ZeroglazeError::MissingRecipientConsent);
+
+    env.update_fee_recipients(&token, &creator, reduced.clone(), &[collaborator])
+        .await
+        .unwrap();
+    assert_eq!(env.bonding_curve(&token).await.fee_recipients, reduced);
+}
+
+#[tokio::test]
+async fn increasing_shares_needs_only_the_creator() {
+    let (mut env, token, creator, collaborators) = setup(&[(6_000, true), (4_000, false)]).await;
+    let newcomer = Keypair::new();
+    let updated = vec![
+        FeeRecipient { wallet: newcomer.pubkey(), weight_bps: 1_000 },
+        FeeRecipient { wallet: creator.pubkey(), weight_bps: 4_000 },
+        FeeRecipient { wallet: collaborators[0].pubkey(), weight_bps: 5_000 },
+    ];
+
+    env.update_fee_recipients(&token, &creator, updated.clone(), &[])
+        .await
+        .unwrap();
+    assert_eq!(env.bonding_curve(&token).await.fee_recipients, updated);
+}
+
+#[tokio::test]
+async fn only_creator_updates_fee_recipients() {
+    let (mut env, token, _, collaborators) = setup(&[(6_000, true), (4_000, false)]).await;
+    let collaborator = &collaborators[0];
+
+    let result = env
+        .update_fee_recipients(
+            &token,
+            collaborator,
+            vec![FeeRecipient { wallet: collaborator.pubkey(), weight_bps: 10_000 }],
+            &[],
+        )
+        .await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
@@ -0,0 +1,136 @@
+use anchor_lang::error::ErrorCode;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+use zeroglaze::constants::{GRADUATION_FEE_SOL, GRADUATION_THRESHOLD_SOL};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze_test_harness::*;
+
+/// Launch a token and buy it up to just below the graduation threshold
+async fn setup_near_graduation() -> (TestEnv, TokenAccounts, Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let whale = env.funded_keypair(200_000_000_000).await;
+    env.buy(&token, &whale, 775_000_000, u64::MAX).await.unwrap();
+
+    let reserves = env.curve_reserves(&token).await;
+    assert!(reserves.real_sol_reserves < GRADUATION_THRESHOLD_SOL);
+    assert!(!reserves.is_graduated());
+    (env, token, whale)
+}
+
+#[tokio::test]
+async fn buy_crossing_threshold_graduates_curve() {
+    let (mut env, token, whale) = setup_near_graduation().await;
+    let vault_rent = env.rent_exempt_minimum(0).await;
+
+    env.advance_clock(1).await;
+    env.buy(&token, &whale, 20_000_000, u64::MAX).await.unwrap();
+
+    let reserves = env.curve_reserves(&token).await;
+    assert!(reserves.is_graduated());
+    assert!(reserves.real_sol_reserves >= GRADUATION_THRESHOLD_SOL);
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, vault_rent + reserves.real_sol_reserves);
+}
+
+#[tokio::test]
+async fn graduated_curve_rejects_trades() {
+    let (mut env, token, whale) = setup_near_graduation().await;
+    env.advance_clock(1).await;
+    env.buy(&token, &whale, 20_000_000, u64::MAX).await.unwrap();
+    let reserves = env.curve_reserves(&token).await;
+
+    env.advance_clock(1).await;
+    let result = env.buy(&token, &whale, 1_000_000, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::TokenGraduated);
+
+    let result = env.sell(&token, &whale, 1_000_000, 0).await;
+    assert_program_error(result, ZeroglazeError::TokenGraduated);
+
+    let after = env.curve_reserves(&token).await;
+    assert_eq!(after.tokens_sold, reserves.tokens_sold);
+    assert_eq!(after.real_sol_reserves, reserves.real_sol_reserves);
+}
+
+#[tokio::test]
+async fn graduated_liquidity_goes_to_dex_liquidity_pda_minus_fee() {
+    let (mut env, token, whale) = setup_near_graduation().await;
+    env.advance_clock(1).await;
+    env.buy(&token, &whale, 20_000_000, u64::MAX).await.unwrap();
+    let reserves = env.curve_reserves(&token).await;
+
+    let authority = env.payer();
+    let dex_liquidity = pda::dex_liquidity(&token.token_mint);
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    env.withdraw_graduated_liquidity(&token, &authority).await.unwrap();
+
+    assert_eq!(env.lamports(&dex_liquidity).await, reserves.real_sol_reserves - GRADUATION_FEE_SOL);
+    assert_eq!(env.lamports(&platform_fee_wallet).await - platform_before, GRADUATION_FEE_SOL);
+    let dex_token_account = anchor_spl::associated_token::get_associated_token_address(
+        &dex_liquidity,
+        &token.token_mint,
+    );
+    assert_eq!(env.token_balance(&dex_token_account).await, reserves.real_token_reserves);
+    assert_eq!(env.token_balance(&token.curve_token_account).await, 0);
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, env.rent_exempt_minimum(0).await);
+
+    let after = env.curve_reserves(&token).await;
+    assert_eq!(after.real_sol_reserves, 0);
+    assert_eq!(after.real_token_reserves, 0);
+
+    let result = env.withdraw_graduated_liquidity(&token, &authority).await;
+    assert_program_error(result, ZeroglazeError::LiquidityAlreadyWithdrawn);
+}
+
+#[tokio::test]
+async fn liquidity_only_goes_to_the_mints_dex_liquidity_pda() {
+    let (mut env, token, whale) = setup_near_graduation().await;
+    env.advance_clock(1).await;
+    env.buy(&token, &whale, 20_000_000, u64::MAX).await.unwrap();
+
+    // The authority's own wallet and token account in place of the PDA's
+    let authority = env.payer().pubkey();
+    let dex_liquidity = pda::dex_liquidity(&token.token_mint);
+    let swaps = [
+        (dex_liquidity, authority),
+        (token.token_account(&dex_liquidity), token.token_account(&authority)),
+    ];
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let mut ix = instructions::withdraw_graduated_liquidity(&token, &authority, &platform_fee_wallet);
+    for account in ix.accounts.iter_mut() {
+        if let Some((_, to)) = swaps.iter().find(|(from, _)| *from == account.pubkey) {
+            account.pubkey = *to;
+        }
+    }
+    let result = env.process(&[ix], &[]).await;
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, ErrorCode::ConstraintSeeds as u32),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn liquidity_withdrawal_requires_graduation() {
+    let (mut env, token, _) = setup_near_graduation().await;
+    let authority = env.payer();
+
+    let result = env.withdraw_graduated_liquidity(&token, &authority).await;
+    assert_program_error(result, ZeroglazeError::TokenNotGraduated);
+}
+
+#[tokio::test]
+async fn only_platform_authority_withdraws_liquidity() {
+    let (mut env, token, whale) = setup_near_graduation().await;
+    env.advance_clock(1).await;
+    env.buy(&token, &whale, 20_000_000, u64::MAX).await.unwrap();
+
+    let stranger = env.funded_keypair(1_000_000_000).await;
+    let result = env.withdraw_graduated_liquidity(&token, &stranger).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
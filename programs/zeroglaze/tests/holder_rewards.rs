@@ -0,0 +1,210 @@
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::CREATOR_FEE_LIVE_BPS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::{calculate_buy_cost, calculate_fee, calculate_sell_output};
+use zeroglaze_test_harness::*;
+
+const SHARE_BPS: u16 = 5_000;
+
+async fn setup() -> (TestEnv, TokenAccounts) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let authority = env.payer();
+    env.set_holder_reward_share(&authority, SHARE_BPS).await.unwrap();
+    (env, token)
+}
+
+async fn go_live(env: &mut TestEnv, token: &TokenAccounts) {
+    let authority = env.payer();
+    env.update_stream_status(token, &authority, true).await.unwrap();
+}
+
+async fn holder(env: &mut TestEnv, token: &TokenAccounts, token_amount: u64) -> Keypair {
+    let holder = env.funded_keypair(10_000_000_000).await;
+    env.buy(token, &holder, token_amount, u64::MAX).await.unwrap();
+    holder
+}
+
+/// Live creator fee a buy of `token_amount` would pay at the curve's current state
+async fn live_buy_creator_fee(env: &mut TestEnv, token: &TokenAccounts, token_amount: u64) -> u64 {
+    let curve = env.curve_reserves(token).await;
+    calculate_buy_cost(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        token_amount,
+        CREATOR_FEE_LIVE_BPS,
+    )
+    .unwrap()
+    .2
+}
+
+/// Claim and return the lamports received
+async fn claim(env: &mut TestEnv, token: &TokenAccounts, holder: &Keypair) -> u64 {
+    let before = env.lamports(&holder.pubkey()).await;
+    env.claim_holder_rewards(token, holder).await.unwrap();
+    env.lamports(&holder.pubkey()).await - before
+}
+
+#[tokio::test]
+async fn live_buy_rewards_existing_holders() {
+    let (mut env, token) = setup().await;
+    let alice = holder(&mut env, &token, 10_000_000).await;
+    go_live(&mut env, &token).await;
+
+    let creator_fee = live_buy_creator_fee(&mut env, &token, 20_000_000).await;
+    let reward = calculate_fee(creator_fee, SHARE_BPS).unwrap();
+    let pool_before = env.lamports(&token.holder_rewards_pool).await;
+    let fees_before = env.curve_reserves(&token).await.creator_fees_collected;
+    let bob = holder(&mut env, &token, 20_000_000).await;
+
+    assert!(reward > 0);
+    assert_eq!(env.lamports(&token.holder_rewards_pool).await - pool_before, reward);
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.holder_rewards_distributed, reward);
+    assert_eq!(curve.reward_eligible_supply, 30_000_000);
+    // The creator keeps the rest of the live fee
+    assert_eq!(curve.creator_fees_collected - fees_before, creator_fee - reward);
+
+    // Alice held the whole eligible supply; Bob earns nothing from his own buy
+    assert_eq!(claim(&mut env, &token, &alice).await, reward);
+    let result = env.claim_holder_rewards(&token, &bob).await;
+    assert_program_error(result, ZeroglazeError::NoRewardsToClaim);
+}
+
+#[tokio::test]
+async fn rewards_split_pro_rata() {
+    let (mut env, token) = setup().await;
+    let alice = holder(&mut env, &token, 10_000_000).await;
+    let bob = holder(&mut env, &token, 30_000_000).await;
+    go_live(&mut env, &token).await;
+
+    let creator_fee = live_buy_creator_fee(&mut env, &token, 50_000_000).await;
+    let reward = calculate_fee(creator_fee, SHARE_BPS).unwrap();
+    holder(&mut env, &token, 50_000_000).await;
+
+    let alice_reward = claim(&mut env, &token, &alice).await;
+    let bob_reward = claim(&mut env, &token, &bob).await;
+
+    // Rounding always favors the pool
+    assert!(alice_reward + bob_reward <= reward);
+    assert!(reward - (alice_reward + bob_reward) <= 1);
+    assert!(alice_reward.abs_diff(reward / 4) <= 1);
+    assert!(bob_reward.abs_diff(reward * 3 / 4) <= 1);
+}
+
+#[tokio::test]
+async fn offline_trades_pay_no_rewards() {
+    let (mut env, token) = setup().await;
+    let alice = holder(&mut env, &token, 10_000_000).await;
+    let pool_before = env.lamports(&token.holder_rewards_pool).await;
+
+    holder(&mut env, &token, 20_000_000).await;
+
+    assert_eq!(env.lamports(&token.holder_rewards_pool).await, pool_before);
+    let result = env.claim_holder_rewards(&token, &alice).await;
+    assert_program_error(result, ZeroglazeError::NoRewardsToClaim);
+}
+
+#[tokio::test]
+async fn no_rewards_without_configured_share() {
+    let (mut env, token) = setup().await;
+    let authority = env.payer();
+    env.set_holder_reward_share(&authority, 0).await.unwrap();
+    holder(&mut env, &token, 10_000_000).await;
+    go_live(&mut env, &token).await;
+
+    let creator_fee = live_buy_creator_fee(&mut env, &token, 20_000_000).await;
+    let fees_before = env.curve_reserves(&token).await.creator_fees_collected;
+    holder(&mut env, &token, 20_000_000).await;
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.holder_rewards_distributed, 0);
+    assert_eq!(curve.creator_fees_collected - fees_before, creator_fee);
+}
+
+#[tokio::test]
+async fn live_sell_rewards_remaining_holders() {
+    let (mut env, token) = setup().await;
+    let alice = holder(&mut env, &token, 10_000_000).await;
+    let bob = holder(&mut env, &token, 10_000_000).await;
+    go_live(&mut env, &token).await;
+
+    let curve = env.curve_reserves(&token).await;
+    let (_, _, creator_fee, _) = calculate_sell_output(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        10_000_000,
+        CREATOR_FEE_LIVE_BPS,
+    )
+    .unwrap();
+    let reward = calculate_fee(creator_fee, SHARE_BPS).unwrap();
+    env.sell(&token, &bob, 10_000_000, 0).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.reward_eligible_supply, 10_000_000);
+    assert_eq!(env.buyer_record(&token, &bob.pubkey()).await.reward_balance, 0);
+
+    // Bob's exit pays Alice, not Bob
+    assert_eq!(claim(&mut env, &token, &alice).await, reward);
+    let result = env.claim_holder_rewards(&token, &bob).await;
+    assert_program_error(result, ZeroglazeError::NoRewardsToClaim);
+}
+
+#[tokio::test]
+async fn transferred_tokens_stop_earning() {
+    let (mut env, token) = setup().await;
+    let alice = holder(&mut env, &token, 10_000_000).await;
+    let bob = holder(&mut env, &token, 10_000_000).await;
+    go_live(&mut env, &token).await;
+    let creator_fee = live_buy_creator_fee(&mut env, &token, 1_000_000).await;
+    let reward = calculate_fee(creator_fee, SHARE_BPS).unwrap();
+    holder(&mut env, &token, 1_000_000).await;
+
+    // Alice moves half her tokens elsewhere; her claim pays only for what she
+    // still holds and shrinks her stake to match
+    let elsewhere = Keypair::new();
+    env.transfer_tokens(&token, &alice, &elsewhere.pubkey(), 5_000_000).await;
+    let paid = claim(&mut env, &token, &alice).await;
+    assert!(paid > 0 && paid <= reward / 4);
+    let record = env.buyer_record(&token, &alice.pubkey()).await;
+    assert_eq!(record.reward_balance, 5_000_000);
+    assert_eq!(env.curve_reserves(&token).await.reward_eligible_supply, 16_000_000);
+    assert!(claim(&mut env, &token, &bob).await > 0);
+}
+
+#[tokio::test]
+async fn transferred_tokens_earn_nothing_before_next_settlement() {
+    let (mut env, token) = setup().await;
+    let alice = holder(&mut env, &token, 10_000_000).await;
+    let bob = holder(&mut env, &token, 10_000_000).await;
+    go_live(&mut env, &token).await;
+
+    // Alice moves her tokens out, then a live trade pays holders before she
+    // interacts with the curve again
+    let elsewhere = Keypair::new();
+    env.transfer_tokens(&token, &alice, &elsewhere.pubkey(), 10_000_000).await;
+    let creator_fee = live_buy_creator_fee(&mut env, &token, 1_000_000).await;
+    let reward = calculate_fee(creator_fee, SHARE_BPS).unwrap();
+    holder(&mut env, &token, 1_000_000).await;
+
+    let result = env.claim_holder_rewards(&token, &alice).await;
+    assert_program_error(result, ZeroglazeError::NoRewardsToClaim);
+
+    // Bob is paid for his own stake; Alice's unsettled share stays in the pool
+    let paid = claim(&mut env, &token, &bob).await;
+    assert!(paid > 0 && paid <= reward / 2);
+}
+
+#[tokio::test]
+async fn holder_reward_share_is_validated() {
+    let (mut env, _) = setup().await;
+    let authority = env.payer();
+
+    let result = env.set_holder_reward_share(&authority, 10_001).await;
+    assert_program_error(result, ZeroglazeError::InvalidHolderRewardShare);
+
+    let intruder = env.funded_keypair(1_000_000_000).await;
+    let result = env.set_holder_reward_share(&intruder, 1_000).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
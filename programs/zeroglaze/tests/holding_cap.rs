@@ -0,0 +1,115 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::constants::CREATOR_SUPPLY;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze_test_harness::*;
+
+// 25% of total supply; the creator's allocation is 20%
+const MAX_HOLDING_BPS: u16 = 2_500;
+const MAX_HOLDING: u64 = 250_000_000;
+
+async fn setup() -> (TestEnv, TokenAccounts, solana_sdk::signature::Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(100_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        max_holding_bps: MAX_HOLDING_BPS,
+        ..TestEnv::create_token_args(false)
+    };
+    let token = env.create_token_with(&creator, args).await.unwrap();
+    (env, token, creator)
+}
+
+#[tokio::test]
+async fn create_rejects_cap_above_total_supply() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(100_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        max_holding_bps: 10_001,
+        ..TestEnv::create_token_args(false)
+    };
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::InvalidHoldingCap);
+}
+
+#[tokio::test]
+async fn create_rejects_cap_below_creator_allocation() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(100_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        max_holding_bps: 1_999,
+        ..TestEnv::create_token_args(false)
+    };
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::InvalidHoldingCap);
+}
+
+#[tokio::test]
+async fn buy_up_to_cap_succeeds() {
+    let (mut env, token, _) = setup().await;
+    let buyer = env.funded_keypair(100_000_000_000).await;
+
+    env.buy(&token, &buyer, MAX_HOLDING, u64::MAX).await.unwrap();
+    assert_eq!(env.token_balance(&token.curve_token_account).await, 800_000_000 - MAX_HOLDING);
+}
+
+#[tokio::test]
+async fn buy_over_cap_fails() {
+    let (mut env, token, _) = setup().await;
+    let buyer = env.funded_keypair(100_000_000_000).await;
+
+    env.buy(&token, &buyer, MAX_HOLDING - 1, u64::MAX).await.unwrap();
+    let result = env.buy(&token, &buyer, 2, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::HoldingCapExceeded);
+}
+
+#[tokio::test]
+async fn cap_counts_tokens_received_outside_the_curve() {
+    let (mut env, token, _) = setup().await;
+    let whale = env.funded_keypair(100_000_000_000).await;
+    let sidekick = env.funded_keypair(100_000_000_000).await;
+
+    env.buy(&token, &whale, MAX_HOLDING, u64::MAX).await.unwrap();
+    env.buy(&token, &sidekick, MAX_HOLDING, u64::MAX).await.unwrap();
+    env.transfer_tokens(&token, &sidekick, &whale.pubkey(), 1).await;
+
+    let result = env.buy(&token, &whale, 1, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::HoldingCapExceeded);
+}
+
+#[tokio::test]
+async fn creator_allocation_counts_towards_cap() {
+    let (mut env, token, creator) = setup().await;
+    let headroom = MAX_HOLDING - CREATOR_SUPPLY;
+
+    env.buy(&token, &creator, headroom, u64::MAX).await.unwrap();
+    let result = env.buy(&token, &creator, 1, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::HoldingCapExceeded);
+}
+
+#[tokio::test]
+async fn initial_buy_within_cap_succeeds() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(100_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        max_holding_bps: MAX_HOLDING_BPS,
+        initial_buy_lamports: Some(1_000_000_000),
+        ..TestEnv::create_token_args(false)
+    };
+    let token = env.create_token_with(&creator, args).await.unwrap();
+    let creator_token_account =
+        anchor_spl::associated_token::get_associated_token_address(&token.creator, &token.token_mint);
+    let balance = env.token_balance(&creator_token_account).await;
+    assert!(balance > CREATOR_SUPPLY && balance <= MAX_HOLDING);
+}
+
+#[tokio::test]
+async fn initial_buy_respects_cap() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(100_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        max_holding_bps: MAX_HOLDING_BPS,
+        initial_buy_lamports: Some(3_000_000_000),
+        ..TestEnv::create_token_args(false)
+    };
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::HoldingCapExceeded);
+}
@@ -0,0 +1,99 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::constants::CREATOR_FEE_OFFLINE_BPS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::{calculate_buy_cost, calculate_tokens_for_sol};
+use zeroglaze_test_harness::*;
+
+const CREATOR_SUPPLY: u64 = 200_000_000;
+
+#[tokio::test]
+async fn create_with_initial_buy_credits_creator() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let initial_buy_lamports = 1_000_000_000;
+    let args = zeroglaze::instruction::CreateToken {
+        initial_buy_lamports: Some(initial_buy_lamports),
+        ..TestEnv::create_token_args(false)
+    };
+
+    let expected_tokens =
+        calculate_tokens_for_sol(30_000_000_000, 1_073_000_000, initial_buy_lamports).unwrap();
+    let (sol_required, platform_fee, creator_fee, total_cost) =
+        calculate_buy_cost(30_000_000_000, 1_073_000_000, expected_tokens, CREATOR_FEE_OFFLINE_BPS).unwrap();
+
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    let token = env.create_token_with(&creator, args).await.unwrap();
+
+    let creator_ata = anchor_spl::associated_token::get_associated_token_address(
+        &creator.pubkey(),
+        &token.token_mint,
+    );
+    assert_eq!(env.token_balance(&creator_ata).await, CREATOR_SUPPLY + expected_tokens);
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.tokens_sold, expected_tokens);
+    assert_eq!(curve.real_sol_reserves, sol_required);
+    assert_eq!(curve.total_volume, total_cost);
+    assert_eq!(curve.creator_fees_collected, creator_fee);
+    assert!(sol_required <= initial_buy_lamports);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + platform_fee);
+
+    let platform = env.platform_state().await;
+    assert_eq!(platform.total_volume_sol, total_cost);
+}
+
+#[tokio::test]
+async fn initial_buy_skips_launch_protection() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        launch_protection_slots: 100,
+        launch_max_buy_lamports: 100_000_000,
+        sniper_fee_bps: 5_000,
+        initial_buy_lamports: Some(1_000_000_000),
+        ..TestEnv::create_token_args(true)
+    };
+    let token = env.create_token_with(&creator, args).await.unwrap();
+
+    // Only the regular offline creator fee was charged
+    let curve = env.curve_reserves(&token).await;
+    let (_, _, creator_fee, _) =
+        calculate_buy_cost(30_000_000_000, 1_073_000_000, curve.tokens_sold, CREATOR_FEE_OFFLINE_BPS).unwrap();
+    assert_eq!(curve.creator_fees_collected, creator_fee);
+}
+
+#[tokio::test]
+async fn create_without_initial_buy_leaves_curve_untouched() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.tokens_sold, 0);
+    assert_eq!(curve.real_sol_reserves, 0);
+}
+
+#[tokio::test]
+async fn initial_buy_cannot_graduate_token() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(200_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        initial_buy_lamports: Some(86_000_000_000),
+        ..TestEnv::create_token_args(false)
+    };
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::InitialBuyTooLarge);
+}
+
+#[tokio::test]
+async fn initial_buy_rejects_dust() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        initial_buy_lamports: Some(1),
+        ..TestEnv::create_token_args(false)
+    };
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::InvalidAmount);
+}
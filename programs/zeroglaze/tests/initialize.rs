@@ -0,0 +1,33 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::constants::{
+    DEFAULT_LIVE_FEE_TIERS_BPS, DEFAULT_MAX_STREAM_STALENESS_SECS, PLATFORM_STATE_VERSION,
+};
+use zeroglaze_test_harness::*;
+
+#[tokio::test]
+async fn initialize_sets_platform_defaults() {
+    let mut env = TestEnv::new().await;
+    let platform = env.platform_state().await;
+
+    assert_eq!(platform.authority, env.payer().pubkey());
+    assert_eq!(platform.platform_fee_wallet, env.platform_fee_wallet.pubkey());
+    assert_eq!(platform.total_tokens_launched, 0);
+    assert_eq!(platform.total_volume_sol, 0);
+    assert_eq!(platform.total_fees_collected, 0);
+    assert_eq!(platform.max_stream_staleness_secs, DEFAULT_MAX_STREAM_STALENESS_SECS);
+    assert_eq!(platform.live_fee_tiers_bps, DEFAULT_LIVE_FEE_TIERS_BPS);
+    assert_eq!(platform.holder_reward_share_bps, 0);
+    assert_eq!(platform.referral_fee_bps, 0);
+    assert_eq!(platform.version, PLATFORM_STATE_VERSION);
+}
+
+#[tokio::test]
+async fn platform_can_only_be_initialized_once() {
+    let mut env = TestEnv::new().await;
+    let intruder = env.funded_keypair(1_000_000_000).await;
+
+    // The platform_state PDA already exists
+    env.advance_clock(1).await;
+    assert!(env.initialize_platform(&intruder).await.is_err());
+    assert_eq!(env.platform_state().await.authority, env.payer().pubkey());
+}
@@ -0,0 +1,117 @@
+use solana_sdk::signature::Keypair;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze_test_harness::*;
+
+async fn setup() -> (TestEnv, TokenAccounts) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    (env, token)
+}
+
+#[tokio::test]
+async fn buy_passes_invariant_check() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    env.buy(&token, &buyer, 1_000_000, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    let vault_rent = env.rent_exempt_minimum(0).await;
+    assert_eq!(env.lamports(&token.curve_sol_vault).await - vault_rent, curve.real_sol_reserves);
+    assert_eq!(env.token_balance(&token.curve_token_account).await, curve.real_token_reserves);
+}
+
+#[tokio::test]
+async fn buy_fails_when_sol_reserves_overstated() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    let mut reserves = env.curve_reserves(&token).await;
+    reserves.real_sol_reserves += 1;
+    env.set_curve_reserves(&token, &reserves).await;
+
+    let result = env.buy(&token, &buyer, 1_000_000, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::InvariantViolation);
+}
+
+#[tokio::test]
+async fn buy_fails_when_token_reserves_overstated() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    // Claim more tokens than the curve account holds, without touching supply checks
+    let mut reserves = env.curve_reserves(&token).await;
+    reserves.real_token_reserves += 1;
+    env.set_curve_reserves(&token, &reserves).await;
+
+    let result = env.buy(&token, &buyer, 1_000_000, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::InvariantViolation);
+}
+
+#[tokio::test]
+async fn buy_tolerates_unsolicited_vault_deposit() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    env.airdrop(&token.curve_sol_vault, 12_345).await;
+    env.buy(&token, &buyer, 1_000_000, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.tokens_sold, 1_000_000);
+}
+
+/// A holder with tokens to sell back into the curve
+async fn setup_seller() -> (TestEnv, TokenAccounts, Keypair) {
+    let (mut env, token) = setup().await;
+    let seller = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &seller, 10_000_000, u64::MAX).await.unwrap();
+    (env, token, seller)
+}
+
+#[tokio::test]
+async fn sell_passes_invariant_check() {
+    let (mut env, token, seller) = setup_seller().await;
+
+    env.sell(&token, &seller, 4_000_000, 0).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    let vault_rent = env.rent_exempt_minimum(0).await;
+    assert_eq!(env.lamports(&token.curve_sol_vault).await - vault_rent, curve.real_sol_reserves);
+    assert_eq!(env.token_balance(&token.curve_token_account).await, curve.real_token_reserves);
+}
+
+#[tokio::test]
+async fn sell_fails_when_sol_reserves_overstated() {
+    let (mut env, token, seller) = setup_seller().await;
+
+    let mut reserves = env.curve_reserves(&token).await;
+    reserves.real_sol_reserves += 1;
+    env.set_curve_reserves(&token, &reserves).await;
+
+    let result = env.sell(&token, &seller, 4_000_000, 0).await;
+    assert_program_error(result, ZeroglazeError::InvariantViolation);
+}
+
+#[tokio::test]
+async fn sell_fails_when_token_reserves_overstated() {
+    let (mut env, token, seller) = setup_seller().await;
+
+    let mut reserves = env.curve_reserves(&token).await;
+    reserves.real_token_reserves += 1;
+    env.set_curve_reserves(&token, &reserves).await;
+
+    let result = env.sell(&token, &seller, 4_000_000, 0).await;
+    assert_program_error(result, ZeroglazeError::InvariantViolation);
+}
+
+#[tokio::test]
+async fn sell_tolerates_unsolicited_vault_deposit() {
+    let (mut env, token, seller) = setup_seller().await;
+
+    env.airdrop(&token.curve_sol_vault, 12_345).await;
+    env.sell(&token, &seller, 4_000_000, 0).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.tokens_sold, 6_000_000);
+}
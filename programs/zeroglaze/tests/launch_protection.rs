@@ -0,0 +1,101 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::constants::CREATOR_FEE_OFFLINE_BPS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::calculate_buy_cost;
+use zeroglaze_test_harness::*;
+
+const PROTECTION_SLOTS: u64 = 100;
+const MAX_BUY_LAMPORTS: u64 = 1_000_000_000;
+const SNIPER_FEE_BPS: u16 = 5_000;
+
+async fn setup() -> (TestEnv, TokenAccounts) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        launch_protection_slots: PROTECTION_SLOTS,
+        launch_max_buy_lamports: MAX_BUY_LAMPORTS,
+        sniper_fee_bps: SNIPER_FEE_BPS,
+        ..TestEnv::create_token_args(false)
+    };
+    let token = env.create_token_with(&creator, args).await.unwrap();
+    (env, token)
+}
+
+#[tokio::test]
+async fn create_rejects_excessive_protection() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let args = zeroglaze::instruction::CreateToken {
+        sniper_fee_bps: 10_000,
+        ..TestEnv::create_token_args(false)
+    };
+    let result = env.create_token_with(&creator, args).await.map(|_| ());
+    assert_program_error(result, ZeroglazeError::InvalidLaunchProtection);
+}
+
+#[tokio::test]
+async fn buy_in_window_pays_sniper_fee_to_creator() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let fee_wallet_before = env.lamports(&token.creator_fee_wallet).await;
+
+    env.buy(&token, &buyer, 1_000_000, u64::MAX).await.unwrap();
+
+    let (sol_required, _, creator_fee, _) =
+        calculate_buy_cost(30_000_000_000, 1_073_000_000, 1_000_000, CREATOR_FEE_OFFLINE_BPS).unwrap();
+    let fee_wallet_gain = env.lamports(&token.creator_fee_wallet).await - fee_wallet_before;
+    let sniper_fee = fee_wallet_gain - creator_fee;
+
+    // Decayed from 50% by however many slots elapsed since launch
+    assert!(sniper_fee > 0);
+    assert!(sniper_fee <= sol_required / 2);
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.creator_fees_collected, fee_wallet_gain);
+
+    let record = env.buyer_record(&token, &buyer.pubkey()).await;
+    assert_eq!(record.buyer, buyer.pubkey());
+    assert_eq!(record.bonding_curve, token.bonding_curve);
+    assert_eq!(record.launch_sol_bought, sol_required);
+}
+
+#[tokio::test]
+async fn buy_in_window_enforces_per_wallet_cap() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    // ~0.56 SOL each; the second buy pushes the wallet over 1 SOL
+    env.buy(&token, &buyer, 20_000_000, u64::MAX).await.unwrap();
+    let result = env.buy(&token, &buyer, 20_000_000, u64::MAX).await;
+    assert_program_error(result, ZeroglazeError::LaunchBuyLimitExceeded);
+
+    // Other wallets have their own allowance
+    let other = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &other, 20_000_000, u64::MAX).await.unwrap();
+}
+
+#[tokio::test]
+async fn protection_lifts_after_window() {
+    let (mut env, token) = setup().await;
+    let buyer = env.funded_keypair(100_000_000_000).await;
+    env.warp_slots(PROTECTION_SLOTS + 1).await;
+
+    let fee_wallet_before = env.lamports(&token.creator_fee_wallet).await;
+    let curve = env.curve_reserves(&token).await;
+    let (_, _, creator_fee, _) = calculate_buy_cost(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        100_000_000,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap();
+
+    // Well over the launch cap, with only the regular creator fee
+    env.buy(&token, &buyer, 100_000_000, u64::MAX).await.unwrap();
+
+    assert_eq!(
+        env.lamports(&token.creator_fee_wallet).await,
+        fee_wallet_before + creator_fee
+    );
+    assert_eq!(env.buyer_record(&token, &buyer.pubkey()).await.launch_sol_bought, 0);
+}
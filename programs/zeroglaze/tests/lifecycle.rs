@@ -0,0 +1,110 @@
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::{CREATOR_FEE_LIVE_BPS, CREATOR_FEE_OFFLINE_BPS};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::{calculate_buy_cost, calculate_sell_output};
+use zeroglaze_test_harness::*;
+
+/// Buy at the curve's current price, checking the trader and fee wallets
+/// against the quote. Returns the total SOL paid.
+async fn quoted_buy(env: &mut TestEnv, token: &TokenAccounts, trader: &Keypair, amount: u64, fee_bps: u16) -> u64 {
+    let reserves = env.curve_reserves(token).await;
+    let (sol_required, platform_fee, creator_fee, total_cost) = calculate_buy_cost(
+        reserves.virtual_sol_reserves,
+        reserves.virtual_token_reserves,
+        amount,
+        fee_bps,
+    )
+    .unwrap();
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let trader_before = env.lamports(&trader.pubkey()).await;
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    let creator_fee_before = env.lamports(&token.creator_fee_wallet).await;
+
+    env.advance_clock(1).await;
+    env.buy(token, trader, amount, total_cost).await.unwrap();
+
+    assert_eq!(env.lamports(&trader.pubkey()).await, trader_before - total_cost);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + platform_fee);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, creator_fee_before + creator_fee);
+    let after = env.curve_reserves(token).await;
+    assert_eq!(after.real_sol_reserves, reserves.real_sol_reserves + sol_required);
+    assert_eq!(after.tokens_sold, reserves.tokens_sold + amount);
+    assert_eq!(after.creator_fees_collected, reserves.creator_fees_collected + creator_fee);
+    total_cost
+}
+
+/// Sell at the curve's current price, checking the trader and fee wallets
+/// against the quote. Returns the SOL taken out of the curve.
+async fn quoted_sell(env: &mut TestEnv, token: &TokenAccounts, trader: &Keypair, amount: u64, fee_bps: u16) -> u64 {
+    let reserves = env.curve_reserves(token).await;
+    let (sol_to_return, platform_fee, creator_fee, net_output) = calculate_sell_output(
+        reserves.virtual_sol_reserves,
+        reserves.virtual_token_reserves,
+        amount,
+        fee_bps,
+    )
+    .unwrap();
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let trader_before = env.lamports(&trader.pubkey()).await;
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    let creator_fee_before = env.lamports(&token.creator_fee_wallet).await;
+
+    env.advance_clock(1).await;
+    env.sell(token, trader, amount, net_output).await.unwrap();
+
+    assert_eq!(env.lamports(&trader.pubkey()).await, trader_before + net_output);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + platform_fee);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, creator_fee_before + creator_fee);
+    let after = env.curve_reserves(token).await;
+    assert_eq!(after.real_sol_reserves, reserves.real_sol_reserves - sol_to_return);
+    assert_eq!(after.tokens_sold, reserves.tokens_sold - amount);
+    assert_eq!(after.creator_fees_collected, reserves.creator_fees_collected + creator_fee);
+    sol_to_return
+}
+
+#[tokio::test]
+async fn token_lifecycle_across_stream_sessions() {
+    let mut env = TestEnv::new().await;
+    let authority = env.payer();
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let trader = env.funded_keypair(10_000_000_000).await;
+    let mut volume = 0;
+
+    // Offline: the reduced creator fee applies. The first buy opens the
+    // trader's accounts, so quoted_buy starts from the second.
+    env.buy(&token, &trader, 1_000_000, u64::MAX).await.unwrap();
+    volume += env.curve_reserves(&token).await.total_volume;
+    volume += quoted_buy(&mut env, &token, &trader, 10_000_000, CREATOR_FEE_OFFLINE_BPS).await;
+
+    // Only the platform authority reports stream status
+    let result = env.update_stream_status(&token, &creator, true).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+    assert!(!env.bonding_curve(&token).await.is_live_streaming);
+
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    assert!(env.bonding_curve(&token).await.is_live_streaming);
+    volume += quoted_buy(&mut env, &token, &trader, 10_000_000, CREATOR_FEE_LIVE_BPS).await;
+    volume += quoted_sell(&mut env, &token, &trader, 5_000_000, CREATOR_FEE_LIVE_BPS).await;
+
+    env.advance_clock(1).await;
+    env.update_stream_status(&token, &authority, false).await.unwrap();
+    assert!(!env.bonding_curve(&token).await.is_live_streaming);
+    volume += quoted_sell(&mut env, &token, &trader, 5_000_000, CREATOR_FEE_OFFLINE_BPS).await;
+
+    let platform = env.platform_state().await;
+    assert_eq!(platform.total_tokens_launched, 1);
+    assert_eq!(platform.total_volume_sol, volume);
+
+    // Only the fee authority withdraws, and it receives everything pending
+    let result = env.withdraw_creator_fees(&token, &trader).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+
+    let pending = env.curve_reserves(&token).await.creator_fees_pending();
+    assert!(pending > 0);
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before + pending);
+    assert_eq!(env.curve_reserves(&token).await.creator_fees_pending(), 0);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, env.rent_exempt_minimum(0).await);
+}
@@ -0,0 +1,133 @@
+use zeroglaze::constants::{
+    CREATOR_FEE_LIVE_BPS, CREATOR_FEE_OFFLINE_BPS, DEFAULT_LIVE_FEE_TIERS_BPS,
+    DEFAULT_MAX_STREAM_STALENESS_SECS, MAX_CREATOR_FEE_BPS,
+};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::calculate_buy_cost;
+use zeroglaze_test_harness::*;
+
+const RAMP: [u16; 4] = [50, 100, 200, 400];
+
+async fn setup() -> (TestEnv, TokenAccounts) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let authority = env.payer();
+    env.set_live_fee_tiers(&authority, RAMP).await.unwrap();
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    (env, token)
+}
+
+/// Buy and check the creator was charged `creator_fee_bps`
+async fn assert_buy_charges(env: &mut TestEnv, token: &TokenAccounts, creator_fee_bps: u16) {
+    let curve = env.curve_reserves(token).await;
+    let (_, _, expected, _) = calculate_buy_cost(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        1_000_000,
+        creator_fee_bps,
+    )
+    .unwrap();
+
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    env.buy(token, &buyer, 1_000_000, u64::MAX).await.unwrap();
+    let charged = env.curve_reserves(token).await.creator_fees_collected - curve.creator_fees_collected;
+    assert_eq!(charged, expected);
+}
+
+#[tokio::test]
+async fn default_tiers_keep_flat_live_fee() {
+    let mut env = TestEnv::new().await;
+    assert_eq!(env.platform_state().await.live_fee_tiers_bps, DEFAULT_LIVE_FEE_TIERS_BPS);
+    assert!(DEFAULT_LIVE_FEE_TIERS_BPS.iter().all(|&bps| bps == CREATOR_FEE_LIVE_BPS));
+}
+
+#[tokio::test]
+async fn live_fee_follows_stream_tier() {
+    let (mut env, token) = setup().await;
+    let authority = env.payer();
+
+    // New tokens start in the lowest tier
+    assert_eq!(env.bonding_curve(&token).await.live_fee_tier, 0);
+    assert_buy_charges(&mut env, &token, RAMP[0]).await;
+
+    for (tier, &creator_fee_bps) in RAMP.iter().enumerate().skip(1) {
+        env.update_stream_tier(&token, &authority, tier as u8).await.unwrap();
+        assert_buy_charges(&mut env, &token, creator_fee_bps).await;
+    }
+}
+
+#[tokio::test]
+async fn offline_fee_ignores_stream_tier() {
+    let (mut env, token) = setup().await;
+    let authority = env.payer();
+    env.update_stream_tier(&token, &authority, 3).await.unwrap();
+
+    env.update_stream_status(&token, &authority, false).await.unwrap();
+    assert_buy_charges(&mut env, &token, CREATOR_FEE_OFFLINE_BPS).await;
+
+    // Stale live status counts as offline too
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS + 1).await;
+    assert_buy_charges(&mut env, &token, CREATOR_FEE_OFFLINE_BPS).await;
+}
+
+#[tokio::test]
+async fn sell_fee_follows_stream_tier() {
+    let (mut env, token) = setup().await;
+    let authority = env.payer();
+    env.update_stream_tier(&token, &authority, 2).await.unwrap();
+
+    let trader = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &trader, 10_000_000, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    let (_, _, expected, _) = zeroglaze::utils::calculate_sell_output(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        5_000_000,
+        RAMP[2],
+    )
+    .unwrap();
+
+    env.sell(&token, &trader, 5_000_000, 0).await.unwrap();
+    let charged = env.curve_reserves(&token).await.creator_fees_collected - curve.creator_fees_collected;
+    assert_eq!(charged, expected);
+}
+
+#[tokio::test]
+async fn stream_tier_must_exist() {
+    let (mut env, token) = setup().await;
+    let authority = env.payer();
+
+    let result = env.update_stream_tier(&token, &authority, RAMP.len() as u8).await;
+    assert_program_error(result, ZeroglazeError::InvalidLiveFeeTier);
+}
+
+#[tokio::test]
+async fn only_authority_updates_stream_tier() {
+    let (mut env, token) = setup().await;
+    let intruder = env.funded_keypair(1_000_000_000).await;
+
+    let result = env.update_stream_tier(&token, &intruder, 1).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
+
+#[tokio::test]
+async fn fee_tiers_are_validated() {
+    let mut env = TestEnv::new().await;
+    let authority = env.payer();
+
+    for tiers in [
+        [100, 50, 200, 400],                         // decreasing
+        [CREATOR_FEE_OFFLINE_BPS - 1, 100, 200, 400], // cheaper than offline
+        [50, 100, 200, MAX_CREATOR_FEE_BPS + 1],      // above the cap
+    ] {
+        let result = env.set_live_fee_tiers(&authority, tiers).await;
+        assert_program_error(result, ZeroglazeError::InvalidLiveFeeTiers);
+    }
+
+    let intruder = env.funded_keypair(1_000_000_000).await;
+    let result = env.set_live_fee_tiers(&intruder, RAMP).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
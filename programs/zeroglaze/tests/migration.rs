@@ -0,0 +1,339 @@
+use anchor_lang::{AccountSerialize, AnchorSerialize, Discriminator, Space};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::{
+    ACCOUNT_RESERVED_BYTES, BONDING_CURVE_VERSION, DEFAULT_LIVE_FEE_TIERS_BPS,
+    DEFAULT_MAX_STREAM_STALENESS_SECS, PLATFORM_STATE_VERSION,
+};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::legacy::{BondingCurveV0, PlatformStateV0};
+use zeroglaze::state::{BondingCurve, PlatformState};
+use zeroglaze_test_harness::*;
+
+async fn raw_data(env: &mut TestEnv, address: &Pubkey) -> Vec<u8> {
+    env.context.banks_client.get_account(*address).await.unwrap().unwrap().data
+}
+
+/// Store `encoded` as a pre-versioning account: the current discriminator
+/// followed by the v0 fields, in an account sized for the v0 layout
+async fn write_v0_account(env: &mut TestEnv, address: &Pubkey, discriminator: [u8; 8], encoded: &[u8], space: usize) {
+    let mut data = vec![0; 8 + space];
+    data[..8].copy_from_slice(&discriminator);
+    data[8..8 + encoded.len()].copy_from_slice(encoded);
+    let account = Account {
+        lamports: env.rent_exempt_minimum(data.len()).await,
+        data,
+        owner: zeroglaze::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    env.context.set_account(address, &account.into());
+}
+
+fn set_lamports(env: &mut TestEnv, address: &Pubkey, lamports: u64) {
+    let account = Account { lamports, ..Account::default() };
+    env.context.set_account(address, &account.into());
+}
+
+/// A token as the original program left it: a single v0 curve account, SOL
+/// PDAs holding only what they owe, and none of the accounts added since
+async fn v0_token(env: &mut TestEnv) -> (TokenAccounts, Keypair, Keypair, BondingCurveV0) {
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &buyer, 10_000_000, u64::MAX).await.unwrap();
+    let reserves = env.curve_reserves(&token).await;
+
+    let v0 = BondingCurveV0 {
+        creator: creator.pubkey(),
+        token_mint: token.token_mint,
+        token_name: "Legacy Token".to_string(),
+        token_symbol: "OLD".to_string(),
+        token_uri: "https://example.com/legacy.json".to_string(),
+        creator_twitter: "@legacy".to_string(),
+        creator_twitch: "legacy_streams".to_string(),
+        freeze_creator_allocation: false,
+        virtual_sol_reserves: reserves.virtual_sol_reserves,
+        virtual_token_reserves: reserves.virtual_token_reserves,
+        real_sol_reserves: reserves.real_sol_reserves,
+        real_token_reserves: reserves.real_token_reserves,
+        tokens_sold: reserves.tokens_sold,
+        total_volume: reserves.total_volume,
+        creator_fees_collected: reserves.creator_fees_collected,
+        is_live_streaming: false,
+        last_stream_check: 1_700_000_000,
+        graduated: false,
+        created_at: 1_700_000_000,
+        bump: env.bonding_curve(&token).await.bump,
+    };
+    write_v0_curve(env, &token, &v0, 0).await;
+    let buyer_record = pda::buyer_record(&token.bonding_curve, &buyer.pubkey());
+    env.context.set_account(&buyer_record, &Account::default().into());
+    (token, creator, buyer, v0)
+}
+
+async fn write_v0_curve(env: &mut TestEnv, token: &TokenAccounts, v0: &BondingCurveV0, creator_fees_withdrawn: u64) {
+    let encoded = v0.try_to_vec().unwrap();
+    write_v0_account(env, &token.bonding_curve, BondingCurve::DISCRIMINATOR, &encoded, BondingCurveV0::INIT_SPACE).await;
+
+    for address in [token.curve_reserves, token.token_metadata, token.stream_history, token.holder_rewards_pool] {
+        env.context.set_account(&address, &Account::default().into());
+    }
+    set_lamports(env, &token.curve_sol_vault, v0.real_sol_reserves);
+    set_lamports(env, &token.creator_fee_wallet, v0.creator_fees_collected - creator_fees_withdrawn);
+}
+
+#[tokio::test]
+async fn v0_curve_migrates_to_current_layout() {
+    let mut env = TestEnv::new().await;
+    let (token, _, _, v0) = v0_token(&mut env).await;
+    // Part of the fees were already paid out by the original program
+    let withdrawn = v0.creator_fees_collected / 3;
+    write_v0_curve(&mut env, &token, &v0, withdrawn).await;
+
+    let payer = env.payer();
+    env.migrate_curve(&token, &payer).await.unwrap();
+
+    let data = raw_data(&mut env, &token.bonding_curve).await;
+    assert_eq!(data.len(), 8 + BondingCurve::INIT_SPACE);
+    let curve = env.bonding_curve(&token).await;
+    assert_eq!(curve.version, BONDING_CURVE_VERSION);
+    assert_eq!(curve.reserved, [0; ACCOUNT_RESERVED_BYTES]);
+    assert_eq!(curve.creator, v0.creator);
+    assert_eq!(curve.token_mint, v0.token_mint);
+    assert_eq!(curve.bump, v0.bump);
+    assert_eq!(curve.created_at, v0.created_at);
+    assert_eq!(curve.last_stream_check, v0.last_stream_check);
+    // Features added since v0 start switched off
+    assert_eq!(curve.fee_authority, v0.creator);
+    assert_eq!(curve.pending_fee_authority, Pubkey::default());
+    assert_eq!(curve.last_attestation_nonce, 0);
+    assert_eq!(curve.launch_protection_slots, 0);
+    assert_eq!(curve.sniper_fee_bps, 0);
+    assert_eq!(curve.max_holding_bps, 0);
+    assert!(curve.fee_recipients.is_empty());
+
+    let reserves = env.curve_reserves(&token).await;
+    assert_eq!(reserves.bonding_curve, token.bonding_curve);
+    assert_eq!(reserves.virtual_sol_reserves, v0.virtual_sol_reserves);
+    assert_eq!(reserves.virtual_token_reserves, v0.virtual_token_reserves);
+    assert_eq!(reserves.real_sol_reserves, v0.real_sol_reserves);
+    assert_eq!(reserves.real_token_reserves, v0.real_token_reserves);
+    assert_eq!(reserves.tokens_sold, v0.tokens_sold);
+    assert_eq!(reserves.total_volume, v0.total_volume);
+    assert_eq!(reserves.creator_fees_collected, v0.creator_fees_collected);
+    assert_eq!(reserves.creator_fees_withdrawn, withdrawn);
+    assert_eq!(reserves.reward_per_token(), 0);
+    assert_eq!(reserves.reward_eligible_supply, 0);
+    assert!(!reserves.is_graduated());
+
+    let metadata = env.token_metadata(&token).await;
+    assert_eq!(metadata.bonding_curve, token.bonding_curve);
+    assert_eq!(metadata.token_name, v0.token_name);
+    assert_eq!(metadata.token_symbol, v0.token_symbol);
+    assert_eq!(metadata.token_uri, v0.token_uri);
+    assert_eq!(metadata.creator_twitter, v0.creator_twitter);
+    assert_eq!(metadata.creator_twitch, v0.creator_twitch);
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.bonding_curve, token.bonding_curve);
+    assert_eq!(history.session_count, 0);
+    assert_eq!(history.current_session_start, 0);
+
+    // The SOL PDAs are funded to rent exemption on top of what they owe
+    let rent = env.rent_exempt_minimum(0).await;
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, rent + v0.real_sol_reserves);
+    assert_eq!(
+        env.lamports(&token.creator_fee_wallet).await,
+        rent + v0.creator_fees_collected - withdrawn
+    );
+    assert_eq!(env.lamports(&token.holder_rewards_pool).await, rent);
+}
+
+#[tokio::test]
+async fn migrated_v0_curve_trades_and_pays_out_fees() {
+    let mut env = TestEnv::new().await;
+    let (token, creator, buyer, v0) = v0_token(&mut env).await;
+    let payer = env.payer();
+    env.migrate_curve(&token, &payer).await.unwrap();
+
+    // v0 holders have no reward stake and can still sell
+    env.advance_clock(1).await;
+    env.sell(&token, &buyer, 1_000_000, 0).await.unwrap();
+    assert_eq!(env.curve_reserves(&token).await.tokens_sold, v0.tokens_sold - 1_000_000);
+    env.buy(&token, &buyer, 1_000_000, u64::MAX).await.unwrap();
+
+    // Fees collected before the migration are still withdrawable
+    let reserves = env.curve_reserves(&token).await;
+    assert!(reserves.creator_fees_collected > v0.creator_fees_collected);
+    let before = env.lamports(&creator.pubkey()).await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+    assert_eq!(env.lamports(&creator.pubkey()).await - before, reserves.creator_fees_collected);
+    assert_eq!(env.curve_reserves(&token).await.creator_fees_pending(), 0);
+}
+
+#[tokio::test]
+async fn live_v0_curve_keeps_its_open_session() {
+    let mut env = TestEnv::new().await;
+    let (token, _, _, mut v0) = v0_token(&mut env).await;
+    v0.is_live_streaming = true;
+    write_v0_curve(&mut env, &token, &v0, 0).await;
+
+    let payer = env.payer();
+    env.migrate_curve(&token, &payer).await.unwrap();
+
+    assert!(env.bonding_curve(&token).await.is_live_streaming);
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.current_session_start, v0.last_stream_check);
+    assert_eq!(history.session_count, 1);
+}
+
+#[tokio::test]
+async fn current_curve_is_not_migrated_again() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    assert_eq!(env.bonding_curve(&token).await.version, BONDING_CURVE_VERSION);
+    let before = raw_data(&mut env, &token.bonding_curve).await;
+
+    // The reserves and metadata accounts already exist, so they cannot be created again
+    let payer = env.payer();
+    let result = env.migrate_curve(&token, &payer).await;
+    assert!(result.is_err());
+    assert_eq!(raw_data(&mut env, &token.bonding_curve).await, before);
+}
+
+/// A launched token whose curve claims `version` and whose split-off
+/// accounts are gone, so migrate_curve reaches the version check
+async fn curve_with_version(env: &mut TestEnv, version: u8) -> TokenAccounts {
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let curve = BondingCurve { version, ..env.bonding_curve(&token).await };
+    let mut data = raw_data(env, &token.bonding_curve).await;
+    curve.try_serialize(&mut &mut data[..]).unwrap();
+    let account = Account {
+        lamports: env.lamports(&token.bonding_curve).await,
+        data,
+        owner: zeroglaze::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    env.context.set_account(&token.bonding_curve, &account.into());
+    for address in [token.curve_reserves, token.token_metadata, token.stream_history] {
+        env.context.set_account(&address, &Account::default().into());
+    }
+    token
+}
+
+#[tokio::test]
+async fn migrate_curve_checks_the_stored_version() {
+    let mut env = TestEnv::new().await;
+    let payer = env.payer();
+
+    let token = curve_with_version(&mut env, BONDING_CURVE_VERSION).await;
+    let result = env.migrate_curve(&token, &payer).await;
+    assert_program_error(result, ZeroglazeError::AccountAlreadyMigrated);
+
+    let token = curve_with_version(&mut env, BONDING_CURVE_VERSION + 1).await;
+    let result = env.migrate_curve(&token, &payer).await;
+    assert_program_error(result, ZeroglazeError::UnsupportedAccountVersion);
+}
+
+#[tokio::test]
+async fn migrate_platform_rejects_unknown_version() {
+    let mut env = TestEnv::new().await;
+    let platform = PlatformState { version: PLATFORM_STATE_VERSION + 1, ..env.platform_state().await };
+    let mut data = raw_data(&mut env, &pda::platform_state()).await;
+    platform.try_serialize(&mut &mut data[..]).unwrap();
+    let account = Account {
+        lamports: env.lamports(&pda::platform_state()).await,
+        data,
+        owner: zeroglaze::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    env.context.set_account(&pda::platform_state(), &account.into());
+
+    let authority = env.payer();
+    let result = env.migrate_platform(&authority).await;
+    assert_program_error(result, ZeroglazeError::UnsupportedAccountVersion);
+}
+
+#[tokio::test]
+async fn migrate_curve_rejects_other_accounts() {
+    let mut env = TestEnv::new().await;
+    let payer = env.payer();
+
+    let mut token = TokenAccounts::new(&payer.pubkey());
+    token.bonding_curve = pda::platform_state();
+    token.curve_reserves = pda::curve_reserves(&token.bonding_curve);
+    token.token_metadata = pda::token_metadata(&token.bonding_curve);
+    token.stream_history = pda::stream_history(&token.bonding_curve);
+    token.curve_sol_vault = pda::curve_sol_vault(&token.bonding_curve);
+    token.holder_rewards_pool = pda::holder_rewards_pool(&token.bonding_curve);
+    let result = env.migrate_curve(&token, &payer).await;
+    assert_program_error(result, ZeroglazeError::InvalidBondingCurve);
+}
+
+#[tokio::test]
+async fn migrate_curve_rejects_wrong_fee_wallet() {
+    let mut env = TestEnv::new().await;
+    let (mut token, _, _, _) = v0_token(&mut env).await;
+    token.creator_fee_wallet = Pubkey::new_unique();
+
+    let payer = env.payer();
+    let result = env.migrate_curve(&token, &payer).await;
+    assert_program_error(result, ZeroglazeError::InvalidBondingCurve);
+}
+
+#[tokio::test]
+async fn v0_platform_state_migrates() {
+    let mut env = TestEnv::new().await;
+    let platform = env.platform_state().await;
+    let v0 = PlatformStateV0 {
+        authority: platform.authority,
+        platform_fee_wallet: platform.platform_fee_wallet,
+        total_tokens_launched: 7,
+        total_volume_sol: 123_000_000_000,
+        total_fees_collected: 1_230_000_000,
+        bump: platform.bump,
+    };
+    let encoded = v0.try_to_vec().unwrap();
+    write_v0_account(&mut env, &pda::platform_state(), PlatformState::DISCRIMINATOR, &encoded, PlatformStateV0::INIT_SPACE).await;
+
+    // Only the platform authority may migrate it
+    let stranger = env.funded_keypair(1_000_000_000).await;
+    let result = env.migrate_platform(&stranger).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+
+    let authority = env.payer();
+    env.migrate_platform(&authority).await.unwrap();
+
+    let data = raw_data(&mut env, &pda::platform_state()).await;
+    assert_eq!(data.len(), 8 + PlatformState::INIT_SPACE);
+    let migrated = env.platform_state().await;
+    assert_eq!(migrated.version, PLATFORM_STATE_VERSION);
+    assert_eq!(migrated.authority, v0.authority);
+    assert_eq!(migrated.platform_fee_wallet, v0.platform_fee_wallet);
+    assert_eq!(migrated.total_tokens_launched, v0.total_tokens_launched);
+    assert_eq!(migrated.total_volume_sol, v0.total_volume_sol);
+    assert_eq!(migrated.total_fees_collected, v0.total_fees_collected);
+    assert_eq!(migrated.bump, v0.bump);
+    // Settings added since v0 take their initialize_platform defaults
+    assert_eq!(migrated.max_stream_staleness_secs, DEFAULT_MAX_STREAM_STALENESS_SECS);
+    assert_eq!(migrated.stream_oracle, Pubkey::default());
+    assert_eq!(migrated.live_fee_tiers_bps, DEFAULT_LIVE_FEE_TIERS_BPS);
+    assert_eq!(migrated.holder_reward_share_bps, 0);
+    assert_eq!(migrated.referral_fee_bps, 0);
+    assert_eq!(migrated.total_referral_fees, 0);
+
+    env.advance_clock(1).await;
+    let result = env.migrate_platform(&authority).await;
+    assert_program_error(result, ZeroglazeError::AccountAlreadyMigrated);
+
+    // Launches work on the migrated state
+    let creator = env.funded_keypair(10_000_000_000).await;
+    env.create_token(&creator, false).await.unwrap();
+    assert_eq!(env.platform_state().await.total_tokens_launched, v0.total_tokens_launched + 1);
+}
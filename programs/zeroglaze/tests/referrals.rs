@@ -0,0 +1,167 @@
+use anchor_lang::prelude::AccountMeta;
+use anchor_lang::Space;
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::CREATOR_FEE_OFFLINE_BPS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::{calculate_buy_cost, calculate_fee, calculate_sell_output};
+use zeroglaze_test_harness::*;
+
+const REFERRAL_BPS: u16 = 2_500;
+
+async fn setup() -> (TestEnv, TokenAccounts, Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let authority = env.payer();
+    env.set_referral_fee(&authority, REFERRAL_BPS).await.unwrap();
+    let referrer = env.funded_keypair(1_000_000_000).await;
+    env.register_referrer(&referrer).await.unwrap();
+    (env, token, referrer)
+}
+
+#[tokio::test]
+async fn referred_buy_pays_referrer_from_platform_fee() {
+    let (mut env, token, referrer) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let (sol_required, platform_fee, creator_fee, total_cost) =
+        calculate_buy_cost(30_000_000_000, 1_073_000_000, 10_000_000, CREATOR_FEE_OFFLINE_BPS).unwrap();
+    let referral_fee = calculate_fee(platform_fee, REFERRAL_BPS).unwrap();
+    assert!(referral_fee > 0);
+
+    let referrer_before = env.lamports(&referrer.pubkey()).await;
+    let platform_before = env.lamports(&env.platform_fee_wallet.pubkey()).await;
+    let buyer_before = env.lamports(&buyer.pubkey()).await;
+    env.buy_referred(&token, &buyer, 10_000_000, &referrer.pubkey()).await.unwrap();
+
+    assert_eq!(env.lamports(&referrer.pubkey()).await - referrer_before, referral_fee);
+    assert_eq!(
+        env.lamports(&env.platform_fee_wallet.pubkey()).await - platform_before,
+        platform_fee - referral_fee
+    );
+
+    // The trader pays the same either way
+    let rent = env.rent_exempt_minimum(8 + zeroglaze::state::BuyerRecord::INIT_SPACE).await
+        + env.rent_exempt_minimum(165).await;
+    assert_eq!(buyer_before - env.lamports(&buyer.pubkey()).await, total_cost + rent);
+    assert_eq!(total_cost, sol_required + platform_fee + creator_fee);
+
+    let stats = env.referrer_stats(&referrer.pubkey()).await;
+    assert_eq!(stats.referrer, referrer.pubkey());
+    assert_eq!(stats.total_fees_earned, referral_fee);
+    assert_eq!(stats.total_volume_referred, total_cost);
+    assert_eq!(stats.trade_count, 1);
+
+    let platform = env.platform_state().await;
+    assert_eq!(platform.total_referral_fees, referral_fee);
+    assert_eq!(platform.total_fees_collected, platform_fee + creator_fee);
+}
+
+#[tokio::test]
+async fn referred_sell_pays_referrer_from_platform_fee() {
+    let (mut env, token, referrer) = setup().await;
+    let trader = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &trader, 20_000_000, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    let (sol_to_return, platform_fee, _, net_output) = calculate_sell_output(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        10_000_000,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap();
+    let referral_fee = calculate_fee(platform_fee, REFERRAL_BPS).unwrap();
+
+    let referrer_before = env.lamports(&referrer.pubkey()).await;
+    let platform_before = env.lamports(&env.platform_fee_wallet.pubkey()).await;
+    let trader_before = env.lamports(&trader.pubkey()).await;
+    env.sell_referred(&token, &trader, 10_000_000, &referrer.pubkey()).await.unwrap();
+
+    assert_eq!(env.lamports(&referrer.pubkey()).await - referrer_before, referral_fee);
+    assert_eq!(
+        env.lamports(&env.platform_fee_wallet.pubkey()).await - platform_before,
+        platform_fee - referral_fee
+    );
+    assert_eq!(env.lamports(&trader.pubkey()).await - trader_before, net_output);
+
+    let stats = env.referrer_stats(&referrer.pubkey()).await;
+    assert_eq!(stats.total_volume_referred, sol_to_return);
+    assert_eq!(stats.total_fees_earned, referral_fee);
+}
+
+#[tokio::test]
+async fn unreferred_trades_pay_full_platform_fee() {
+    let (mut env, token, referrer) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let (_, platform_fee, _, _) =
+        calculate_buy_cost(30_000_000_000, 1_073_000_000, 10_000_000, CREATOR_FEE_OFFLINE_BPS).unwrap();
+
+    let platform_before = env.lamports(&env.platform_fee_wallet.pubkey()).await;
+    env.buy(&token, &buyer, 10_000_000, u64::MAX).await.unwrap();
+
+    assert_eq!(env.lamports(&env.platform_fee_wallet.pubkey()).await - platform_before, platform_fee);
+    assert_eq!(env.referrer_stats(&referrer.pubkey()).await.trade_count, 0);
+    assert_eq!(env.platform_state().await.total_referral_fees, 0);
+}
+
+#[tokio::test]
+async fn self_referral_is_rejected() {
+    let (mut env, token, referrer) = setup().await;
+
+    let result = env.buy_referred(&token, &referrer, 10_000_000, &referrer.pubkey()).await;
+    assert_program_error(result, ZeroglazeError::InvalidReferrer);
+}
+
+#[tokio::test]
+async fn referrer_must_match_stats() {
+    let (mut env, token, referrer) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let impostor = Keypair::new();
+
+    // Someone else's wallet paired with the registered referrer's stats
+    let mut ix = instructions::buy_tokens(
+        &token,
+        &env.platform_fee_wallet.pubkey(),
+        &buyer.pubkey(),
+        10_000_000,
+        u64::MAX,
+        Some(&referrer.pubkey()),
+    );
+    let position = ix
+        .accounts
+        .iter()
+        .position(|meta| meta.pubkey == referrer.pubkey())
+        .unwrap();
+    ix.accounts[position] = AccountMeta::new(impostor.pubkey(), false);
+
+    let result = env.process(&[ix], &[&buyer]).await;
+    assert_program_error(result, ZeroglazeError::InvalidReferrer);
+}
+
+#[tokio::test]
+async fn platform_fee_wallet_must_match_platform_state() {
+    let (mut env, token, _) = setup().await;
+    let buyer = env.funded_keypair(10_000_000_000).await;
+
+    let mut ix = instructions::buy_tokens(&token, &env.platform_fee_wallet.pubkey(), &buyer.pubkey(), 10_000_000, u64::MAX, None);
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == platform_fee_wallet) {
+        *meta = AccountMeta::new(buyer.pubkey(), false);
+    }
+
+    let result = env.process(&[ix], &[&buyer]).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
+
+#[tokio::test]
+async fn referral_fee_is_validated() {
+    let (mut env, _, _) = setup().await;
+    let authority = env.payer();
+
+    let result = env.set_referral_fee(&authority, 10_001).await;
+    assert_program_error(result, ZeroglazeError::InvalidReferralFee);
+
+    let intruder = env.funded_keypair(1_000_000_000).await;
+    let result = env.set_referral_fee(&intruder, 1_000).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
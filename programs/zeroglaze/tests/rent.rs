@@ -0,0 +1,94 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze_test_harness::*;
+
+#[tokio::test]
+async fn create_token_funds_sol_pdas_to_rent_exemption() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+
+    let rent = env.rent_exempt_minimum(0).await;
+    for wallet in [token.curve_sol_vault, token.creator_fee_wallet] {
+        let account = env
+            .context
+            .banks_client
+            .get_account(wallet)
+            .await
+            .unwrap()
+            .expect("PDA not created");
+        assert_eq!(account.owner, anchor_lang::system_program::ID);
+        assert_eq!(account.lamports, rent);
+    }
+}
+
+#[tokio::test]
+async fn create_token_tops_up_prefunded_pda() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let expected = TokenAccounts::new(&creator.pubkey());
+    let rent = env.rent_exempt_minimum(0).await;
+    env.airdrop(&expected.curve_sol_vault, rent + 1).await;
+
+    let token = env.create_token(&creator, false).await.unwrap();
+
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, rent + 1);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, rent);
+}
+
+#[tokio::test]
+async fn dust_sized_first_buy_succeeds() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(1_000_000_000).await;
+    let rent = env.rent_exempt_minimum(0).await;
+
+    // A single base unit costs a few dozen lamports with sub-lamport fees
+    env.buy(&token, &buyer, 1, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.tokens_sold, 1);
+    assert!(curve.real_sol_reserves > 0);
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, rent + curve.real_sol_reserves);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, rent);
+}
+
+#[tokio::test]
+async fn dust_sized_creator_fee_is_collected() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(1_000_000_000).await;
+    let rent = env.rent_exempt_minimum(0).await;
+
+    // Smallest buy whose offline creator fee rounds to a single lamport
+    env.buy(&token, &buyer, 20, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    assert_eq!(curve.creator_fees_collected, 1);
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, rent + 1);
+}
+
+#[tokio::test]
+async fn withdraw_leaves_rent_exempt_minimum() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let rent = env.rent_exempt_minimum(0).await;
+
+    env.buy(&token, &buyer, 10_000_000, u64::MAX).await.unwrap();
+    let fees = env.curve_reserves(&token).await.creator_fees_collected;
+    assert!(fees > 0);
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.withdraw_creator_fees(&token, &creator).await.unwrap();
+
+    assert_eq!(env.lamports(&token.creator_fee_wallet).await, rent);
+    assert_eq!(env.lamports(&creator.pubkey()).await, creator_before + fees);
+
+    // Nothing left above the reserve
+    let result = env.withdraw_creator_fees(&token, &creator).await;
+    assert_program_error(result, ZeroglazeError::NoFeesToWithdraw);
+}
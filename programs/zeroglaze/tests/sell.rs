@@ -0,0 +1,47 @@
+use solana_sdk::signature::Signer;
+use zeroglaze::constants::CREATOR_FEE_OFFLINE_BPS;
+use zeroglaze::utils::calculate_sell_output;
+use zeroglaze_test_harness::*;
+
+#[tokio::test]
+async fn sell_moves_lamports_to_every_party_exactly() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let trader = env.funded_keypair(10_000_000_000).await;
+
+    env.buy(&token, &trader, 50_000_000, u64::MAX).await.unwrap();
+
+    let curve = env.curve_reserves(&token).await;
+    let sell_amount = 20_000_000;
+    let (sol_to_return, platform_fee, creator_fee, net_output) = calculate_sell_output(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        sell_amount,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap();
+
+    let platform_fee_wallet = env.platform_fee_wallet.pubkey();
+    let seller_before = env.lamports(&trader.pubkey()).await;
+    let vault_before = env.lamports(&token.curve_sol_vault).await;
+    let platform_before = env.lamports(&platform_fee_wallet).await;
+    let creator_fee_before = env.lamports(&token.creator_fee_wallet).await;
+
+    env.sell(&token, &trader, sell_amount, net_output).await.unwrap();
+
+    // The transaction fee is paid by the environment payer, not the seller
+    assert_eq!(env.lamports(&trader.pubkey()).await, seller_before + net_output);
+    assert_eq!(env.lamports(&token.curve_sol_vault).await, vault_before - sol_to_return);
+    assert_eq!(env.lamports(&platform_fee_wallet).await, platform_before + platform_fee);
+    assert_eq!(
+        env.lamports(&token.creator_fee_wallet).await,
+        creator_fee_before + creator_fee
+    );
+    assert_eq!(sol_to_return, net_output + platform_fee + creator_fee);
+
+    let after = env.curve_reserves(&token).await;
+    assert_eq!(after.real_sol_reserves, curve.real_sol_reserves - sol_to_return);
+    assert_eq!(after.real_token_reserves, curve.real_token_reserves + sell_amount);
+    assert_eq!(after.tokens_sold, curve.tokens_sold - sell_amount);
+}
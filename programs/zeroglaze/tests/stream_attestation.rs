@@ -0,0 +1,161 @@
+use anchor_lang::AnchorSerialize;
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::DEFAULT_MAX_STREAM_STALENESS_SECS;
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::state::StreamAttestation;
+use zeroglaze_test_harness::*;
+
+async fn setup() -> (TestEnv, TokenAccounts, Keypair) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let oracle = Keypair::new();
+    let authority = env.payer();
+    env.set_stream_oracle(&authority, oracle.pubkey()).await.unwrap();
+    (env, token, oracle)
+}
+
+async fn attestation(env: &mut TestEnv, token: &TokenAccounts, is_live: bool, nonce: u64) -> StreamAttestation {
+    StreamAttestation {
+        token_mint: token.token_mint,
+        is_live,
+        timestamp: env.bonding_curve(token).await.last_stream_check,
+        nonce,
+    }
+}
+
+#[tokio::test]
+async fn relayed_attestation_updates_stream_status() {
+    let (mut env, token, oracle) = setup().await;
+    env.advance_clock(30).await;
+    let mut attestation = attestation(&mut env, &token, true, 1).await;
+    attestation.timestamp += 20;
+
+    // The oracle never signs the transaction; the payer just relays it
+    env.relay_stream_attestation(&token, &oracle, attestation.clone())
+        .await
+        .unwrap();
+
+    let curve = env.bonding_curve(&token).await;
+    assert!(curve.is_live_streaming);
+    assert_eq!(curve.last_stream_check, attestation.timestamp);
+    assert_eq!(curve.last_attestation_nonce, 1);
+}
+
+#[tokio::test]
+async fn attestation_nonce_must_increase() {
+    let (mut env, token, oracle) = setup().await;
+    let live = attestation(&mut env, &token, true, 5).await;
+    env.relay_stream_attestation(&token, &oracle, live).await.unwrap();
+
+    for nonce in [5, 4] {
+        let offline = attestation(&mut env, &token, false, nonce).await;
+        let result = env.relay_stream_attestation(&token, &oracle, offline).await;
+        assert_program_error(result, ZeroglazeError::AttestationReplayed);
+    }
+    assert!(env.bonding_curve(&token).await.is_live_streaming);
+}
+
+#[tokio::test]
+async fn attestation_must_be_signed_by_registered_oracle() {
+    let (mut env, token, _) = setup().await;
+    let impostor = Keypair::new();
+    let attestation = attestation(&mut env, &token, true, 1).await;
+
+    let result = env.relay_stream_attestation(&token, &impostor, attestation).await;
+    assert_program_error(result, ZeroglazeError::InvalidAttestation);
+}
+
+#[tokio::test]
+async fn attestation_must_match_signed_message() {
+    let (mut env, token, oracle) = setup().await;
+    let signed = attestation(&mut env, &token, false, 1).await;
+    let mut tampered = signed.clone();
+    tampered.is_live = true;
+
+    let ixs = [
+        ed25519_verify_ix(&oracle, &signed.try_to_vec().unwrap()),
+        instructions::relay_stream_attestation(&token, tampered),
+    ];
+    let result = env.process(&ixs, &[]).await;
+    assert_program_error(result, ZeroglazeError::InvalidAttestation);
+}
+
+#[tokio::test]
+async fn attestation_signature_is_verified() {
+    let (mut env, token, oracle) = setup().await;
+    let attestation = attestation(&mut env, &token, true, 1).await;
+    let message = attestation.try_to_vec().unwrap();
+
+    // Oracle's key in the instruction, but the signature is someone else's
+    let mut forged = ed25519_verify_ix(&Keypair::new(), &message);
+    forged.data[16..48].copy_from_slice(oracle.pubkey().as_ref());
+    let ixs = [forged, instructions::relay_stream_attestation(&token, attestation)];
+
+    assert!(env.process(&ixs, &[]).await.is_err());
+    assert!(!env.bonding_curve(&token).await.is_live_streaming);
+}
+
+#[tokio::test]
+async fn attestation_requires_ed25519_instruction() {
+    let (mut env, token, _) = setup().await;
+    let attestation = attestation(&mut env, &token, true, 1).await;
+
+    let result = env
+        .process(&[instructions::relay_stream_attestation(&token, attestation)], &[])
+        .await;
+    assert_program_error(result, ZeroglazeError::InvalidAttestation);
+}
+
+#[tokio::test]
+async fn attestation_is_bound_to_token_mint() {
+    let (mut env, token, oracle) = setup().await;
+    let other_creator = env.funded_keypair(10_000_000_000).await;
+    let other = env.create_token(&other_creator, false).await.unwrap();
+    let attestation = attestation(&mut env, &other, true, 1).await;
+
+    let result = env.relay_stream_attestation(&token, &oracle, attestation).await;
+    assert_program_error(result, ZeroglazeError::InvalidAttestation);
+}
+
+#[tokio::test]
+async fn stale_attestation_is_rejected() {
+    let (mut env, token, oracle) = setup().await;
+    let attestation = attestation(&mut env, &token, true, 1).await;
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS + 1).await;
+
+    let result = env.relay_stream_attestation(&token, &oracle, attestation).await;
+    assert_program_error(result, ZeroglazeError::StaleAttestation);
+}
+
+#[tokio::test]
+async fn attestation_older_than_last_update_is_rejected() {
+    let (mut env, token, oracle) = setup().await;
+    let attestation = attestation(&mut env, &token, true, 1).await;
+    env.advance_clock(10).await;
+    let authority = env.payer();
+    env.update_stream_status(&token, &authority, false).await.unwrap();
+
+    let result = env.relay_stream_attestation(&token, &oracle, attestation).await;
+    assert_program_error(result, ZeroglazeError::StaleAttestation);
+}
+
+#[tokio::test]
+async fn attestations_disabled_without_oracle() {
+    let (mut env, token, oracle) = setup().await;
+    let authority = env.payer();
+    env.set_stream_oracle(&authority, Default::default()).await.unwrap();
+    let attestation = attestation(&mut env, &token, true, 1).await;
+
+    let result = env.relay_stream_attestation(&token, &oracle, attestation).await;
+    assert_program_error(result, ZeroglazeError::OracleNotConfigured);
+}
+
+#[tokio::test]
+async fn only_authority_sets_stream_oracle() {
+    let (mut env, _, _) = setup().await;
+    let intruder = env.funded_keypair(1_000_000_000).await;
+
+    let result = env.set_stream_oracle(&intruder, intruder.pubkey()).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}
@@ -0,0 +1,139 @@
+use solana_sdk::signature::{Keypair, Signer};
+use zeroglaze::constants::{DEFAULT_MAX_STREAM_STALENESS_SECS, STREAM_HISTORY_LEN};
+use zeroglaze::state::{StreamAttestation, StreamSession};
+use zeroglaze_test_harness::*;
+
+async fn setup() -> (TestEnv, TokenAccounts, Keypair, i64) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let authority = env.payer();
+    let created_at = env.bonding_curve(&token).await.created_at;
+    (env, token, authority, created_at)
+}
+
+#[tokio::test]
+async fn new_token_has_empty_history() {
+    let (mut env, token, _, _) = setup().await;
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.bonding_curve, token.bonding_curve);
+    assert_eq!(history.total_live_secs, 0);
+    assert_eq!(history.session_count, 0);
+    assert_eq!(history.current_session_start, 0);
+    assert_eq!(history.sessions, [StreamSession::default(); STREAM_HISTORY_LEN]);
+}
+
+#[tokio::test]
+async fn sessions_accumulate_live_time() {
+    let (mut env, token, authority, start) = setup().await;
+
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.session_count, 1);
+    assert_eq!(history.current_session_start, start);
+
+    // Heartbeats keep the session open
+    env.advance_clock(100).await;
+    env.stream_heartbeat(&token, &authority).await.unwrap();
+    env.advance_clock(100).await;
+    env.update_stream_status(&token, &authority, false).await.unwrap();
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.total_live_secs, 200);
+    assert_eq!(history.current_session_start, 0);
+    assert_eq!(history.sessions[0], StreamSession { start, end: start + 200 });
+
+    env.advance_clock(50).await;
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    env.advance_clock(30).await;
+    env.update_stream_status(&token, &authority, false).await.unwrap();
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.session_count, 2);
+    assert_eq!(history.total_live_secs, 230);
+    assert_eq!(history.sessions[1], StreamSession { start: start + 250, end: start + 280 });
+}
+
+#[tokio::test]
+async fn repeated_offline_reports_do_not_open_sessions() {
+    let (mut env, token, authority, _) = setup().await;
+
+    env.update_stream_status(&token, &authority, false).await.unwrap();
+    env.advance_clock(10).await;
+    env.stream_heartbeat(&token, &authority).await.unwrap();
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.session_count, 0);
+    assert_eq!(history.total_live_secs, 0);
+}
+
+#[tokio::test]
+async fn unconfirmed_session_ends_when_it_went_stale() {
+    let (mut env, token, authority, start) = setup().await;
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+
+    // Keeper goes quiet for an hour, then reports the stream live again
+    env.advance_clock(3_600).await;
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.total_live_secs, DEFAULT_MAX_STREAM_STALENESS_SECS as u64);
+    assert_eq!(
+        history.sessions[0],
+        StreamSession { start, end: start + DEFAULT_MAX_STREAM_STALENESS_SECS }
+    );
+    assert_eq!(history.session_count, 2);
+    assert_eq!(history.current_session_start, start + 3_600);
+}
+
+#[tokio::test]
+async fn ring_buffer_keeps_most_recent_sessions() {
+    let (mut env, token, authority, start) = setup().await;
+
+    let sessions = STREAM_HISTORY_LEN as i64 + 2;
+    for _ in 0..sessions {
+        env.update_stream_status(&token, &authority, true).await.unwrap();
+        env.advance_clock(10).await;
+        env.update_stream_status(&token, &authority, false).await.unwrap();
+        env.advance_clock(10).await;
+    }
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.session_count as i64, sessions);
+    assert_eq!(history.total_live_secs as i64, sessions * 10);
+    assert_eq!(history.next_session_index, 2);
+
+    // The two oldest slots were overwritten by sessions 8 and 9
+    assert_eq!(history.sessions[0], StreamSession { start: start + 160, end: start + 170 });
+    assert_eq!(history.sessions[1], StreamSession { start: start + 180, end: start + 190 });
+    assert_eq!(history.sessions[2], StreamSession { start: start + 40, end: start + 50 });
+}
+
+#[tokio::test]
+async fn batch_and_attested_updates_are_recorded() {
+    let (mut env, token, authority, start) = setup().await;
+    let oracle = Keypair::new();
+    env.set_stream_oracle(&authority, oracle.pubkey())
+        .await
+        .unwrap();
+
+    env.batch_update_stream_status(&[&token], &authority, vec![true])
+        .await
+        .unwrap();
+    env.advance_clock(40).await;
+    let attestation = StreamAttestation {
+        token_mint: token.token_mint,
+        is_live: false,
+        timestamp: start + 40,
+        nonce: 1,
+    };
+    env.relay_stream_attestation(&token, &oracle, attestation)
+        .await
+        .unwrap();
+
+    let history = env.stream_history(&token).await;
+    assert_eq!(history.session_count, 1);
+    assert_eq!(history.total_live_secs, 40);
+    assert_eq!(history.sessions[0], StreamSession { start, end: start + 40 });
+}
@@ -0,0 +1,149 @@
+use zeroglaze::constants::{
+    CREATOR_FEE_LIVE_BPS, CREATOR_FEE_OFFLINE_BPS, DEFAULT_MAX_STREAM_STALENESS_SECS,
+};
+use zeroglaze::errors::ZeroglazeError;
+use zeroglaze::utils::calculate_buy_cost;
+use zeroglaze_test_harness::*;
+
+/// Buy and return the creator fee that was charged
+async fn buy_creator_fee(env: &mut TestEnv, token: &TokenAccounts, token_amount: u64) -> u64 {
+    let buyer = env.funded_keypair(10_000_000_000).await;
+    let before = env.curve_reserves(token).await.creator_fees_collected;
+    env.buy(token, &buyer, token_amount, u64::MAX).await.unwrap();
+    env.curve_reserves(token).await.creator_fees_collected - before
+}
+
+/// Creator fee the curve would charge for a buy in its current state
+async fn expected_creator_fee(
+    env: &mut TestEnv,
+    token: &TokenAccounts,
+    token_amount: u64,
+    creator_fee_bps: u16,
+) -> u64 {
+    let curve = env.curve_reserves(token).await;
+    calculate_buy_cost(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        token_amount,
+        creator_fee_bps,
+    )
+    .unwrap()
+    .2
+}
+
+async fn setup_live() -> (TestEnv, TokenAccounts) {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let authority = env.payer();
+    env.update_stream_status(&token, &authority, true).await.unwrap();
+    (env, token)
+}
+
+#[tokio::test]
+async fn initialize_sets_default_staleness() {
+    let mut env = TestEnv::new().await;
+    let platform = env.platform_state().await;
+    assert_eq!(platform.max_stream_staleness_secs, DEFAULT_MAX_STREAM_STALENESS_SECS);
+}
+
+#[tokio::test]
+async fn fresh_live_status_charges_live_fee() {
+    let (mut env, token) = setup_live().await;
+
+    let expected = expected_creator_fee(&mut env, &token, 1_000_000, CREATOR_FEE_LIVE_BPS).await;
+    assert_eq!(buy_creator_fee(&mut env, &token, 1_000_000).await, expected);
+}
+
+#[tokio::test]
+async fn stale_live_status_falls_back_to_offline_fee() {
+    let (mut env, token) = setup_live().await;
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS + 1).await;
+
+    let expected = expected_creator_fee(&mut env, &token, 1_000_000, CREATOR_FEE_OFFLINE_BPS).await;
+    assert_eq!(buy_creator_fee(&mut env, &token, 1_000_000).await, expected);
+
+    // The stored status itself is untouched
+    assert!(env.bonding_curve(&token).await.is_live_streaming);
+}
+
+#[tokio::test]
+async fn heartbeat_refreshes_without_changing_status() {
+    let (mut env, token) = setup_live().await;
+    let authority = env.payer();
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS + 1).await;
+    let stale_check = env.bonding_curve(&token).await.last_stream_check;
+
+    env.stream_heartbeat(&token, &authority).await.unwrap();
+
+    let curve = env.bonding_curve(&token).await;
+    assert!(curve.is_live_streaming);
+    assert!(curve.last_stream_check > stale_check);
+
+    let expected = expected_creator_fee(&mut env, &token, 1_000_000, CREATOR_FEE_LIVE_BPS).await;
+    assert_eq!(buy_creator_fee(&mut env, &token, 1_000_000).await, expected);
+}
+
+#[tokio::test]
+async fn heartbeat_keeps_offline_status() {
+    let mut env = TestEnv::new().await;
+    let creator = env.funded_keypair(10_000_000_000).await;
+    let token = env.create_token(&creator, false).await.unwrap();
+    let authority = env.payer();
+
+    env.stream_heartbeat(&token, &authority).await.unwrap();
+    assert!(!env.bonding_curve(&token).await.is_live_streaming);
+}
+
+#[tokio::test]
+async fn stale_status_applies_to_sells() {
+    let (mut env, token) = setup_live().await;
+    let trader = env.funded_keypair(10_000_000_000).await;
+    env.buy(&token, &trader, 10_000_000, u64::MAX).await.unwrap();
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS + 1).await;
+
+    let curve = env.curve_reserves(&token).await;
+    let (_, _, creator_fee, _) = zeroglaze::utils::calculate_sell_output(
+        curve.virtual_sol_reserves,
+        curve.virtual_token_reserves,
+        5_000_000,
+        CREATOR_FEE_OFFLINE_BPS,
+    )
+    .unwrap();
+
+    env.sell(&token, &trader, 5_000_000, 0).await.unwrap();
+    let after = env.curve_reserves(&token).await;
+    assert_eq!(after.creator_fees_collected - curve.creator_fees_collected, creator_fee);
+}
+
+#[tokio::test]
+async fn authority_can_change_staleness() {
+    let (mut env, token) = setup_live().await;
+    let authority = env.payer();
+
+    env.set_max_stream_staleness(&authority, 3_600).await.unwrap();
+    assert_eq!(env.platform_state().await.max_stream_staleness_secs, 3_600);
+
+    // Still live well past the default window
+    env.advance_clock(DEFAULT_MAX_STREAM_STALENESS_SECS * 2).await;
+    let expected = expected_creator_fee(&mut env, &token, 1_000_000, CREATOR_FEE_LIVE_BPS).await;
+    assert_eq!(buy_creator_fee(&mut env, &token, 1_000_000).await, expected);
+
+    let result = env.set_max_stream_staleness(&authority, 0).await;
+    assert_program_error(result, ZeroglazeError::InvalidStreamStaleness);
+}
+
+#[tokio::test]
+async fn only_authority_can_update_stream_or_staleness() {
+    let (mut env, token) = setup_live().await;
+    let intruder = env.funded_keypair(1_000_000_000).await;
+
+    let result = env.stream_heartbeat(&token, &intruder).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+
+    let result = env.update_stream_status(&token, &intruder, false).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+
+    let result = env.set_max_stream_staleness(&intruder, 1).await;
+    assert_program_error(result, ZeroglazeError::Unauthorized);
+}